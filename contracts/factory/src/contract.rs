@@ -2,13 +2,30 @@ use cosmwasm_std::{
     entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, 
     Response, StdResult, Reply,
  };
- use cw2::set_contract_version;
+ use cw2::{get_contract_version, set_contract_version};
+ use semver::Version;
  
  use crate::error::ContractError;
- use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
- use crate::state::{Config, CONFIG, COLLECTION_COUNT};
- use crate::execute::{create_collection, update_nft_code_id, reply_collection_created, update_royalties};
- use crate::query::{query_config, query_collection, query_all_collections, query_artist_collections,query_is_symbol_available};
+ use crate::helpers::validate_pass_terms;
+ use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+ use crate::state::{
+    migrate_state, Config, CONFIG, COLLECTION_COUNT, PENDING_INSTANTIATIONS, PENDING_MIGRATIONS,
+};
+ use crate::execute::{
+    create_collection, update_nft_code_id, reply_collection_created, update_royalties,
+    migrate_collections, reply_collection_migrated, propose_new_admin, accept_admin,
+    cancel_admin_proposal, renounce_admin, grant_role, revoke_role, update_self_service,
+    update_artist_list, validate_self_service, update_collection_bounds,
+    update_config, sync_collections, deactivate_collection, reactivate_collection,
+    archive_collection, update_validation_policy, reserve_symbol, release_reservation,
+};
+ use crate::query::{
+    query_config, query_collection, query_all_collections, query_artist_collections,
+    query_is_symbol_available, query_predict_collection_address, query_collection_code_ids,
+    query_pending_admin, query_roles, query_role_members, query_artist_list,
+    query_collection_by_address, query_collection_by_symbol, query_collections_by_minter,
+    query_collections, query_archived_collections,
+};
  
  const CONTRACT_NAME: &str = "crates.io:loop-factory";
  const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -25,24 +42,36 @@ use cosmwasm_std::{
     if msg.house_percentage + msg.artist_percentage != 100 {
         return Err(ContractError::InvalidRoyalties {});
     }
+
+    validate_pass_terms(msg.duration, msg.grace_period)?;
+    let payment_address = deps.api.addr_validate(msg.payment_address.as_str())?;
+
+    let self_service = msg.self_service.unwrap_or_default();
+    validate_self_service(&self_service)?;
+    let bounds = msg.bounds.unwrap_or_default();
+    bounds.validate()?;
+    let validation = msg.validation.unwrap_or_default();
+    validation.validate()?;
  
     let config = Config {
-        admin: info.sender,
+        admin: Some(info.sender.clone()),
         duration: msg.duration,
         grace_period: msg.grace_period,
-        payment_address: msg.payment_address,
+        payment_address,
         price: msg.price,
         nft_code_id: msg.nft_code_id,
         house_percentage: msg.house_percentage,
-        artist_percentage: msg.artist_percentage
-        
+        artist_percentage: msg.artist_percentage,
+        self_service,
+        bounds,
+        validation,
     };
     CONFIG.save(deps.storage, &config)?;
     COLLECTION_COUNT.save(deps.storage, &0u64)?;
  
     Ok(Response::new()
         .add_attribute("method", "instantiate")
-        .add_attribute("admin", config.admin.to_string())
+        .add_attribute("admin", info.sender)
         .add_attribute("nft_code_id", msg.nft_code_id.to_string()))
  }
  
@@ -59,37 +88,138 @@ use cosmwasm_std::{
             symbol,
             artist,
             minter, 
-            collection_info
-        } => create_collection(deps, env, info, name, symbol, artist, minter, collection_info),
+            collection_info,
+            overrides,
+        } => create_collection(deps, env, info, name, symbol, artist, minter, collection_info, overrides),
         
         ExecuteMsg::UpdateNftCodeId { code_id } => 
             update_nft_code_id(deps, info, code_id),
 
         ExecuteMsg::UpdateRoyalties { house_percentage, artist_percentage } => 
-            update_royalties(deps, info, house_percentage, artist_percentage)
+            update_royalties(deps, info, house_percentage, artist_percentage),
+
+        ExecuteMsg::ProposeNewAdmin { new_admin, expires } =>
+            propose_new_admin(deps, env, info, new_admin, expires),
+
+        ExecuteMsg::AcceptAdmin {} => accept_admin(deps, env, info),
+
+        ExecuteMsg::CancelAdminProposal {} => cancel_admin_proposal(deps, info),
+
+        ExecuteMsg::RenounceAdmin {} => renounce_admin(deps, info),
+
+        ExecuteMsg::UpdateConfig { price, duration, grace_period, payment_address } =>
+            update_config(deps, info, price, duration, grace_period, payment_address),
+
+        ExecuteMsg::SyncCollections { target, fields } => sync_collections(deps, info, target, fields),
+
+        ExecuteMsg::DeactivateCollection { symbol } => deactivate_collection(deps, info, symbol),
+
+        ExecuteMsg::ReactivateCollection { symbol } => reactivate_collection(deps, info, symbol),
+
+        ExecuteMsg::ArchiveCollection { symbol, symbol_policy } =>
+            archive_collection(deps, info, symbol, symbol_policy),
+
+        ExecuteMsg::UpdateCollectionBounds { bounds } => update_collection_bounds(deps, info, bounds),
+
+        ExecuteMsg::UpdateValidationPolicy { policy } => update_validation_policy(deps, info, policy),
+
+        ExecuteMsg::ReserveSymbol { symbol, artist, expires } =>
+            reserve_symbol(deps, env, info, symbol, artist, expires),
+
+        ExecuteMsg::ReleaseReservation { symbol } => release_reservation(deps, info, symbol),
+
+        ExecuteMsg::UpdateSelfService { config } => update_self_service(deps, info, config),
+
+        ExecuteMsg::UpdateArtistList { add, remove } => update_artist_list(deps, info, add, remove),
+
+        ExecuteMsg::GrantRole { role, address } => grant_role(deps, info, role, address),
+
+        ExecuteMsg::RevokeRole { role, address } => revoke_role(deps, info, role, address),
+
+        ExecuteMsg::MigrateCollections { new_code_id, start_after, limit, msg } =>
+            migrate_collections(deps, info, new_code_id, start_after, limit, msg),
     }
  }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => 
             to_json_binary(&query_config(deps)?),
+        QueryMsg::PendingAdmin {} =>
+            to_json_binary(&query_pending_admin(deps)?),
+        QueryMsg::Roles { address } =>
+            to_json_binary(&query_roles(deps, address)?),
+        QueryMsg::RoleMembers { role, start_after, limit } =>
+            to_json_binary(&query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::ArtistList { start_after, limit } =>
+            to_json_binary(&query_artist_list(deps, start_after, limit)?),
         QueryMsg::Collection { artist } => 
             to_json_binary(&query_collection(deps, artist)?),
-        QueryMsg::ArtistCollections { artist, limit } =>
-            to_json_binary(&query_artist_collections(deps, artist, limit)?),
-        QueryMsg::AllCollections { limit } =>
-            to_json_binary(&query_all_collections(deps, limit)?),
+        QueryMsg::CollectionBySymbol { symbol } =>
+            to_json_binary(&query_collection_by_symbol(deps, symbol)?),
+        QueryMsg::CollectionsByMinter { minter, start_after, limit } =>
+            to_json_binary(&query_collections_by_minter(deps, minter, start_after, limit)?),
+        QueryMsg::Collections { symbols } =>
+            to_json_binary(&query_collections(deps, symbols)?),
+        QueryMsg::CollectionByAddress { contract_address } =>
+            to_json_binary(&query_collection_by_address(deps, contract_address)?),
+        QueryMsg::ArtistCollections { artist, start_after, limit, order, filter } =>
+            to_json_binary(&query_artist_collections(deps, artist, start_after, limit, order, filter)?),
+        QueryMsg::AllCollections { start_after, limit, order, filter, include_inactive } =>
+            to_json_binary(&query_all_collections(
+                deps,
+                start_after,
+                limit,
+                order,
+                filter,
+                include_inactive.unwrap_or(false),
+            )?),
         QueryMsg::IsSymbolAvailable { symbol } => 
-            to_json_binary(&query_is_symbol_available(deps, symbol)?),
+            to_json_binary(&query_is_symbol_available(deps, env, symbol)?),
+        QueryMsg::PredictCollectionAddress { symbol } =>
+            to_json_binary(&query_predict_collection_address(deps, env, symbol)?),
+        QueryMsg::CollectionCodeIds { start_after, limit } =>
+            to_json_binary(&query_collection_code_ids(deps, start_after, limit)?),
+        QueryMsg::ArchivedCollections { start_after, limit } =>
+            to_json_binary(&query_archived_collections(deps, start_after, limit)?),
     }
 }
 
  #[cfg_attr(not(feature = "library"), entry_point)]
- pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
-        1 => reply_collection_created(deps, msg),
+        id if PENDING_INSTANTIATIONS.has(deps.storage, id) => reply_collection_created(deps, msg),
+        id if PENDING_MIGRATIONS.has(deps.storage, id) => reply_collection_migrated(deps, env, msg),
         id => Err(ContractError::UnknownReplyId { id }),
     }
- }
\ No newline at end of file
+ }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidMigrationContract {
+            expected: CONTRACT_NAME.to_string(),
+            found: stored.contract,
+        });
+    }
+
+    let stored_version: Version = stored.version.parse()?;
+    let current_version: Version = CONTRACT_VERSION.parse()?;
+    if stored_version > current_version {
+        return Err(ContractError::MigrationDowngrade {
+            stored: stored.version,
+            current: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    let removed = migrate_state(deps.storage, &stored_version)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("removed_collections", removed.join(",")))
+}
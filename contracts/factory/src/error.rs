@@ -1,18 +1,48 @@
 use cosmwasm_std::StdError;
+use cw_utils::{ParseReplyError, PaymentError};
 use thiserror::Error;
 
+use crate::state::Role;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
     // Factory Contract Errors
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Unauthorized: missing role {role}")]
+    MissingRole { role: Role },
+
     #[error("Invalid symbol format. Must be uppercase and no spaces")]
     InvalidSymbol {},
 
+    #[error("Symbol must be {min} to {max} characters long")]
+    InvalidSymbolLength { min: u32, max: u32 },
+
+    #[error("Symbol contains characters outside the allowed charset")]
+    InvalidSymbolCharacters {},
+
+    #[error("Symbol {symbol} is reserved")]
+    ReservedSymbol { symbol: String },
+
+    #[error("Name must be 1 to {max} characters long")]
+    InvalidNameLength { max: u32 },
+
+    #[error("Collection info must be a URI with an allowed scheme")]
+    InvalidCollectionInfo {},
+
+    #[error("Invalid validation policy")]
+    InvalidValidationPolicy {},
+
     #[error("No data in reply")]
     InvalidSymbolA {},
 
@@ -25,12 +55,77 @@ pub enum ContractError {
     #[error("Symbol is already taken")]
     SymbolAlreadyTaken {},
 
+    #[error("Symbol is reserved for {artist}")]
+    SymbolReserved { artist: String },
+
+    #[error("No reservation for this symbol")]
+    ReservationNotFound {},
+
+    #[error("Reservation expiry must be in the future")]
+    InvalidReservationExpiry {},
+
     #[error("Collection not found")]
     CollectionNotFound {},
 
+    #[error("Collection is not active")]
+    CollectionNotActive {},
+
+    #[error("Collection is not inactive")]
+    CollectionNotInactive {},
+
+    #[error("Collection is archived")]
+    CollectionArchived {},
+
+    #[error("No fields to sync")]
+    NoFieldsToSync {},
+
+    #[error("Too many collections, at most {max} per call")]
+    TooManyCollections { max: u32 },
+
+    #[error("Override of {field} is outside the factory bounds")]
+    OverrideOutOfBounds { field: String },
+
+    #[error("Denom {denom} is not accepted")]
+    UnsupportedDenom { denom: String },
+
+    #[error("A collection priced in {denom} needs a price override")]
+    PriceRequiredForDenom { denom: String },
+
+    #[error("Invalid collection bounds")]
+    InvalidBounds {},
+
+    // Self-service Errors
+    #[error("Self-service collections must be created by the artist")]
+    NotArtist {},
+
+    #[error("Artist is not allowed to create collections")]
+    ArtistNotAllowed {},
+
+    #[error("Artist reached the limit of {limit} collections")]
+    ArtistCollectionLimitReached { limit: u32 },
+
+    #[error("Invalid creation fee, expected {expected}")]
+    InvalidCreationFee { expected: String },
+
+    #[error("No admin transfer is pending")]
+    NoPendingAdmin {},
+
+    #[error("Admin transfer proposal has expired")]
+    AdminProposalExpired {},
+
     #[error("Unknown reply ID: {id}")]
     UnknownReplyId { id: u64 },
 
+    // Migration Errors
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    InvalidMigrationContract { expected: String, found: String },
+
+    #[error("Cannot migrate from version {stored} down to {current}")]
+    MigrationDowngrade { stored: String, current: String },
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
     // NFT Contract Errors
     #[error("No uxion payment found")]
     NoPayment {},
@@ -81,4 +176,10 @@ pub enum ContractError {
 
     #[error("Invalid contract instantiation")]
     InvalidInstantiation {},
-}
\ No newline at end of file
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
+}
@@ -1,13 +1,30 @@
 use cosmwasm_std::{
-    to_json_binary, Addr, DepsMut, Env, MessageInfo, Reply, Response, SubMsg, WasmMsg
+    to_json_binary, Addr, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdResult, Storage, SubMsg, SubMsgResult, WasmMsg
  };
-use pass_nft::msg::InstantiateMsg as NftInstantiateMsg;
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, nonpayable, Expiration};
+use pass_nft::msg::{
+    ConfigResponse as NftConfigResponse, ConfigUpdate, ExecuteMsg as NftExecuteMsg, InstantiateMsg as NftInstantiateMsg,
+    PassMsg, PassQuery, QueryMsg as NftQueryMsg,
+};
+use pass_nft::state::{default_revenue_split, DEFAULT_DENOM};
  use cw_utils::parse_reply_instantiate_data;
  
  use crate::error::ContractError;
- use crate::state::{Collection, CONFIG, COLLECTIONS, SYMBOL_TAKEN, save_new_collection};
- use crate::msg::CollectionCreatedEvent;
+ use crate::helpers::{
+    collection_salt, page_limit, predict_collection_address, validate_pass_terms, MAX_LIMIT,
+};
+ use crate::state::{
+    ensure_role, next_reply_id, remove_collection, save_new_collection, ArtistListMode, Collection,
+    CollectionBounds, CollectionOverrides, CollectionStatus, Config, archived_collections,
+    SYMBOL_GENERATION, MigrationStatus, PendingAdmin, Role, SelfServiceConfig, ROLES, CONFIG, collections,
+    MIGRATION_STATUS, PENDING_ADMIN, PENDING_INSTANTIATIONS, PENDING_MIGRATIONS, SYMBOL_TAKEN,
+    ARTIST_LIST, ValidationPolicy, SymbolReservation, SYMBOL_RESERVATIONS, active_reservation,
+};
+ use crate::msg::{CollectionCreatedEvent, CollectionUpdatedEvent, SymbolPolicy, SyncField, SyncTarget};
  
+ #[allow(clippy::too_many_arguments)]
  pub fn create_collection(
     deps: DepsMut,
     env: Env,
@@ -17,46 +34,69 @@ use pass_nft::msg::InstantiateMsg as NftInstantiateMsg;
     artist: Addr,
     minter: Addr,
     collection_info: String,
+    overrides: Option<CollectionOverrides>,
  ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
-
-if !symbol.chars().all(char::is_uppercase) {
-    return Err(ContractError::InvalidSymbol {});
-}
+    let fee_msg = authorize_creation(deps.storage, &config, &info, &artist)?;
+    config.validation.check_collection(&name, &symbol, &collection_info)?;
+    let terms = config.collection_terms(overrides.unwrap_or_default())?;
  
     if SYMBOL_TAKEN.may_load(deps.storage, symbol.clone())?.unwrap_or(false) {
         return Err(ContractError::SymbolAlreadyTaken {});
     }
+    // A reserved symbol goes to its artist, expired reservations are ignored
+    if let Some(reservation) = active_reservation(deps.storage, &env.block, &symbol)? {
+        if reservation.artist != artist {
+            return Err(ContractError::SymbolReserved { artist: reservation.artist.to_string() });
+        }
+    }
 
-    let house_royalty = config.house_percentage;
-    let artist_royalty = config.artist_percentage;
- 
     let instantiate_msg = to_json_binary(&NftInstantiateMsg {
         name: name.clone(),
-        pass_duration: config.duration,
+        pass_duration: terms.pass_duration,
         symbol: symbol.clone(),
         collection_info: collection_info.clone(),
         minter: minter.clone(),
-        pass_price: config.price,
-        grace_period: config.grace_period,
+        pass_price: terms.pass_price,
+        pass_denom: Some(terms.pass_denom.clone()),
+        additional_prices: None,
+        cw20_prices: None,
+        payment_policy: None,
+        max_prepaid_duration: None,
+        holder_policy: None,
+        max_supply: None,
+        max_per_wallet: None,
+        grace_period: terms.grace_period,
         payment_address: config.payment_address,
         artist: artist.clone(),
-        house_percentage : house_royalty.clone(),
-        artist_percentage: artist_royalty.clone()
+        house_percentage: terms.house_percentage,
+        artist_percentage: terms.artist_percentage,
+        revenue_split: None,
     })?;
+
+    // Each instantiation gets its own reply id so the reply can find its
+    // collection even when several are created in the same transaction.
+    let reply_id = next_reply_id(deps.storage)?;
+    PENDING_INSTANTIATIONS.save(deps.storage, reply_id, &symbol)?;
  
+    // Instantiate2 makes the collection address known up front
+    let salt = collection_salt(deps.storage, &symbol)?;
+    let contract_address =
+        predict_collection_address(deps.as_ref(), &env, config.nft_code_id, &symbol)?;
+ 
+    // Only a successful instantiation replies, a failure reverts the whole
+    // transaction along with the registry writes and the creation fee
     let sub_msg = SubMsg::reply_on_success(
-        WasmMsg::Instantiate {
-            admin: Some(config.admin.to_string()),
+        WasmMsg::Instantiate2 {
+            // The factory stays wasm admin so it can migrate its collections
+            admin: Some(env.contract.address.to_string()),
             code_id: config.nft_code_id,
             msg: instantiate_msg,
             funds: vec![],
             label: format!("{} Collection", name),
+            salt,
         },
-        1,
+        reply_id,
     );
 
     let created_at = env.block.time.seconds();
@@ -66,11 +106,10 @@ if !symbol.chars().all(char::is_uppercase) {
         symbol.clone(),
         artist.clone(),
         minter.clone(),
-        Addr::unchecked(""),
+        contract_address.clone(),
         created_at,
         collection_info,
-        house_royalty.clone(),
-        artist_royalty.clone()
+        terms.clone(),
     );
  
     save_new_collection(deps.storage, &collection)?;
@@ -80,26 +119,483 @@ if !symbol.chars().all(char::is_uppercase) {
         symbol,
         artist,
         minter,
-        contract_address: Addr::unchecked(""),
-        house_percentage: house_royalty,
-        artist_percentage: artist_royalty
+        contract_address: contract_address.clone(),
+        house_percentage: terms.house_percentage,
+        artist_percentage: terms.artist_percentage,
+        pass_price: terms.pass_price,
+        pass_denom: terms.pass_denom,
+        pass_duration: terms.pass_duration,
+        grace_period: terms.grace_period,
     };
  
     Ok(Response::new()
         .add_submessage(sub_msg)
+        .add_messages(fee_msg)
         .add_attribute("action", "create_collection")
+        .add_attribute("contract_address", contract_address)
         .add_attribute("event", to_json_binary(&event)?.to_string()))
  }
  
+/// Checks the sender may create a collection for `artist`. Role holders
+/// create for free; anyone else goes through self-service and pays the
+/// creation fee, which is forwarded to the payment address.
+fn authorize_creation(
+    storage: &dyn Storage,
+    config: &Config,
+    info: &MessageInfo,
+    artist: &Addr,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let role_check = ensure_role(storage, config, &info.sender, Role::CollectionCreator);
+    let self_service = &config.self_service;
+    if role_check.is_ok() || !self_service.enabled {
+        role_check?;
+        nonpayable(info)?;
+        return Ok(None);
+    }
+
+    if info.sender != *artist {
+        return Err(ContractError::NotArtist {});
+    }
+
+    let listed = ARTIST_LIST.has(storage, artist);
+    match self_service.list_mode {
+        ArtistListMode::Allowlist if !listed => return Err(ContractError::ArtistNotAllowed {}),
+        ArtistListMode::Denylist if listed => return Err(ContractError::ArtistNotAllowed {}),
+        _ => {}
+    }
+
+    if let Some(limit) = self_service.max_collections_per_artist {
+        let created = collections()
+            .idx
+            .artist
+            .prefix(artist.clone())
+            .keys_raw(storage, None, None, Order::Ascending)
+            .count();
+        if created >= limit as usize {
+            return Err(ContractError::ArtistCollectionLimitReached { limit });
+        }
+    }
+
+    let Some(fee) = &self_service.creation_fee else {
+        nonpayable(info)?;
+        return Ok(None);
+    };
+    let paid = must_pay(info, &fee.denom)?;
+    if paid != fee.amount {
+        return Err(ContractError::InvalidCreationFee { expected: fee.to_string() });
+    }
+
+    Ok(Some(BankMsg::Send {
+        to_address: config.payment_address.to_string(),
+        amount: vec![fee.clone()],
+    }.into()))
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    price: Option<u128>,
+    duration: Option<u64>,
+    grace_period: Option<u64>,
+    payment_address: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    ensure_role(deps.storage, &config, &info.sender, Role::Treasury)?;
+
+    let mut res = Response::new().add_attribute("action", "update_config");
+    let mut diff = |field: &str, old: String, new: String| {
+        if old != new {
+            res = std::mem::take(&mut res)
+                .add_attribute(format!("old_{}", field), old)
+                .add_attribute(format!("new_{}", field), new);
+        }
+    };
+
+    if let Some(price) = price {
+        diff("price", config.price.to_string(), price.to_string());
+        config.price = price;
+    }
+    if let Some(duration) = duration {
+        diff("duration", config.duration.to_string(), duration.to_string());
+        config.duration = duration;
+    }
+    if let Some(grace_period) = grace_period {
+        diff("grace_period", config.grace_period.to_string(), grace_period.to_string());
+        config.grace_period = grace_period;
+    }
+    if let Some(payment_address) = payment_address {
+        let payment_address = deps.api.addr_validate(&payment_address)?;
+        diff("payment_address", config.payment_address.to_string(), payment_address.to_string());
+        config.payment_address = payment_address;
+    }
+
+    validate_pass_terms(config.duration, config.grace_period)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(res)
+}
+
+pub fn sync_collections(
+    deps: DepsMut,
+    info: MessageInfo,
+    target: SyncTarget,
+    fields: Vec<SyncField>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_role(deps.storage, &config, &info.sender, Role::Treasury)?;
+
+    if fields.is_empty() {
+        return Err(ContractError::NoFieldsToSync {});
+    }
+
+    let entries = match target {
+        SyncTarget::Symbols { symbols } => {
+            if symbols.len() > MAX_LIMIT as usize {
+                return Err(ContractError::TooManyCollections { max: MAX_LIMIT });
+            }
+            symbols
+                .into_iter()
+                .map(|symbol| {
+                    collections()
+                        .may_load(deps.storage, symbol.clone())?
+                        .map(|collection| (symbol, collection))
+                        .ok_or(ContractError::CollectionNotFound {})
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        SyncTarget::All { start_after, limit } => {
+            let start = start_after.map(Bound::exclusive);
+            collections()
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .collect::<StdResult<Vec<_>>>()?
+        }
+    };
+
+    let sync = |field: SyncField| fields.contains(&field);
+
+    let mut msgs = Vec::with_capacity(entries.len());
+    let mut skipped = vec![];
+    for (symbol, mut collection) in entries.iter().cloned() {
+        // The factory price is in uxion, collections priced in another denom keep theirs
+        let price = sync(SyncField::Price) && collection.pass_denom == DEFAULT_DENOM;
+        // Percentages would replace a custom revenue split with a two-way one
+        let royalties = sync(SyncField::Royalties) && !has_custom_split(deps.as_ref(), &collection)?;
+        if (sync(SyncField::Price) && !price) || (sync(SyncField::Royalties) && !royalties) {
+            skipped.push(symbol.clone());
+        }
+
+        // Keep the registry in line with what the collection now uses
+        if royalties {
+            collection.house_percentage = config.house_percentage;
+            collection.artist_percentage = config.artist_percentage;
+        }
+        if price {
+            collection.pass_price = config.price;
+        }
+        if sync(SyncField::Duration) {
+            collection.pass_duration = config.duration;
+        }
+        if sync(SyncField::GracePeriod) {
+            collection.grace_period = config.grace_period;
+        }
+        collections().save(deps.storage, symbol, &collection)?;
+
+        let update = ConfigUpdate {
+            payment_address: sync(SyncField::PaymentAddress).then(|| config.payment_address.to_string()),
+            pass_price: price.then_some(config.price),
+            pass_duration: sync(SyncField::Duration).then_some(config.duration),
+            grace_period: sync(SyncField::GracePeriod).then_some(config.grace_period),
+            house_percentage: royalties.then_some(config.house_percentage),
+            artist_percentage: royalties.then_some(config.artist_percentage),
+            ..Default::default()
+        };
+        if update == ConfigUpdate::default() {
+            continue;
+        }
+        msgs.push(WasmMsg::Execute {
+            contract_addr: collection.contract_address.to_string(),
+            msg: to_json_binary(&NftExecuteMsg::Extension { msg: PassMsg::UpdateConfig(update) })?,
+            funds: vec![],
+        });
+    }
+
+    let last_symbol = entries
+        .last()
+        .map(|(symbol, _)| symbol.clone())
+        .unwrap_or_default();
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "sync_collections")
+        .add_attribute("count", entries.len().to_string())
+        .add_attribute("last_symbol", last_symbol)
+        .add_attribute("skipped", skipped.join(",")))
+}
+
+/// Whether the collection pays out along a split other than its house and
+/// artist percentages
+fn has_custom_split(deps: Deps, collection: &Collection) -> StdResult<bool> {
+    let config: NftConfigResponse = deps.querier.query_wasm_smart(
+        collection.contract_address.to_string(),
+        &NftQueryMsg::Extension { msg: PassQuery::GetConfig {} },
+    )?;
+    Ok(config.revenue_split != default_revenue_split(config.house_percentage, config.artist_percentage))
+}
+
+pub fn deactivate_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_role(deps.storage, &config, &info.sender, Role::CollectionCreator)?;
+
+    let mut collection = load_collection(deps.storage, &symbol)?;
+    match collection.status {
+        CollectionStatus::Active => {}
+        CollectionStatus::Inactive => return Err(ContractError::CollectionNotActive {}),
+        CollectionStatus::Archived => return Err(ContractError::CollectionArchived {}),
+    }
+    collection.status = CollectionStatus::Inactive;
+    collections().save(deps.storage, symbol, &collection)?;
+
+    collection_status_response("deactivate_collection", &collection)
+}
+
+pub fn reactivate_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_role(deps.storage, &config, &info.sender, Role::CollectionCreator)?;
+
+    let mut collection = load_collection(deps.storage, &symbol)?;
+    match collection.status {
+        CollectionStatus::Inactive => {}
+        CollectionStatus::Active => return Err(ContractError::CollectionNotInactive {}),
+        CollectionStatus::Archived => return Err(ContractError::CollectionArchived {}),
+    }
+    collection.status = CollectionStatus::Active;
+    collections().save(deps.storage, symbol, &collection)?;
+
+    collection_status_response("reactivate_collection", &collection)
+}
+
+pub fn archive_collection(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+    symbol_policy: SymbolPolicy,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    let mut collection = load_collection(deps.storage, &symbol)?;
+    if collection.status == CollectionStatus::Archived {
+        return Err(ContractError::CollectionArchived {});
+    }
+    collection.status = CollectionStatus::Archived;
+
+    match symbol_policy {
+        SymbolPolicy::Retire => collections().save(deps.storage, symbol.clone(), &collection)?,
+        SymbolPolicy::Release => {
+            // Free the symbol and keep the entry aside, under the generation it was created with
+            let generation = SYMBOL_GENERATION.may_load(deps.storage, symbol.clone())?.unwrap_or_default();
+            remove_collection(deps.storage, &symbol)?;
+            MIGRATION_STATUS.remove(deps.storage, symbol.clone());
+            archived_collections().save(deps.storage, (symbol.clone(), generation), &collection)?;
+            SYMBOL_GENERATION.save(deps.storage, symbol, &(generation + 1))?;
+        }
+    }
+
+    Ok(collection_status_response("archive_collection", &collection)?
+        .add_attribute("symbol_policy", match symbol_policy {
+            SymbolPolicy::Retire => "retire",
+            SymbolPolicy::Release => "release",
+        }))
+}
+
+fn load_collection(storage: &dyn Storage, symbol: &str) -> Result<Collection, ContractError> {
+    collections()
+        .may_load(storage, symbol.to_string())?
+        .ok_or(ContractError::CollectionNotFound {})
+}
+
+/// Pauses or unpauses the pass_nft to match the collection status
+fn collection_status_response(action: &str, collection: &Collection) -> Result<Response, ContractError> {
+    let is_active = collection.status == CollectionStatus::Active;
+    let pause_msg = WasmMsg::Execute {
+        contract_addr: collection.contract_address.to_string(),
+        msg: to_json_binary(&NftExecuteMsg::Extension {
+            msg: PassMsg::SetPaused { paused: !is_active },
+        })?,
+        funds: vec![],
+    };
+    let event = CollectionUpdatedEvent {
+        symbol: collection.symbol.clone(),
+        is_active,
+        status: collection.status,
+    };
+
+    Ok(Response::new()
+        .add_message(pause_msg)
+        .add_attribute("action", action)
+        .add_attribute("symbol", &collection.symbol)
+        .add_attribute("event", to_json_binary(&event)?.to_string()))
+}
+
+pub fn update_collection_bounds(
+    deps: DepsMut,
+    info: MessageInfo,
+    bounds: CollectionBounds,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    bounds.validate()?;
+    config.bounds = bounds;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_collection_bounds"))
+}
+
+pub fn update_validation_policy(
+    deps: DepsMut,
+    info: MessageInfo,
+    policy: ValidationPolicy,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    policy.validate()?;
+    config.validation = policy;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_validation_policy"))
+}
+
+pub fn reserve_symbol(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    symbol: String,
+    artist: String,
+    expires: Expiration,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    let artist = deps.api.addr_validate(&artist)?;
+    config.validation.check_symbol(&symbol)?;
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidReservationExpiry {});
+    }
+    if SYMBOL_TAKEN.may_load(deps.storage, symbol.clone())?.unwrap_or(false) {
+        return Err(ContractError::SymbolAlreadyTaken {});
+    }
+    // The admin can extend a reservation, but not hand it to someone else
+    if let Some(reservation) = active_reservation(deps.storage, &env.block, &symbol)? {
+        if reservation.artist != artist {
+            return Err(ContractError::SymbolReserved { artist: reservation.artist.to_string() });
+        }
+    }
+
+    SYMBOL_RESERVATIONS.save(deps.storage, symbol.clone(), &SymbolReservation {
+        artist: artist.clone(),
+        expires,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reserve_symbol")
+        .add_attribute("symbol", symbol)
+        .add_attribute("artist", artist)
+        .add_attribute("expires", expires.to_string()))
+}
+
+pub fn release_reservation(
+    deps: DepsMut,
+    info: MessageInfo,
+    symbol: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let reservation = SYMBOL_RESERVATIONS
+        .may_load(deps.storage, symbol.clone())?
+        .ok_or(ContractError::ReservationNotFound {})?;
+    if config.admin.as_ref() != Some(&info.sender) && reservation.artist != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    SYMBOL_RESERVATIONS.remove(deps.storage, symbol.clone());
+
+    Ok(Response::new()
+        .add_attribute("action", "release_reservation")
+        .add_attribute("symbol", symbol)
+        .add_attribute("artist", reservation.artist))
+}
+
+pub fn update_self_service(
+    deps: DepsMut,
+    info: MessageInfo,
+    self_service: SelfServiceConfig,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    validate_self_service(&self_service)?;
+    config.self_service = self_service;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_self_service")
+        .add_attribute("enabled", config.self_service.enabled.to_string())
+        .add_attribute(
+            "creation_fee",
+            config.self_service.creation_fee.map_or("none".to_string(), |fee| fee.to_string()),
+        ))
+}
+
+pub fn validate_self_service(self_service: &SelfServiceConfig) -> Result<(), ContractError> {
+    if let Some(fee) = &self_service.creation_fee {
+        if fee.amount.is_zero() || fee.denom.is_empty() {
+            return Err(ContractError::InvalidCreationFee { expected: "a non-zero fee".to_string() });
+        }
+    }
+    Ok(())
+}
+
+pub fn update_artist_list(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    for artist in &add {
+        let artist = deps.api.addr_validate(artist)?;
+        ARTIST_LIST.save(deps.storage, &artist, &true)?;
+    }
+    for artist in &remove {
+        let artist = deps.api.addr_validate(artist)?;
+        ARTIST_LIST.remove(deps.storage, &artist);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_artist_list")
+        .add_attribute("added", add.len().to_string())
+        .add_attribute("removed", remove.len().to_string()))
+}
+
  pub fn update_nft_code_id(
     deps: DepsMut,
     info: MessageInfo,
     code_id: u64,
  ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    ensure_role(deps.storage, &config, &info.sender, Role::CodeManager)?;
  
     config.nft_code_id = code_id;
     CONFIG.save(deps.storage, &config)?;
@@ -113,39 +609,25 @@ if !symbol.chars().all(char::is_uppercase) {
     deps: DepsMut,
     reply: Reply,
 ) -> Result<Response, ContractError> {
-    let res = parse_reply_instantiate_data(reply).unwrap();
-    let contract_addr = deps.api.addr_validate(&res.contract_address)?;
+    let symbol = PENDING_INSTANTIATIONS.load(deps.storage, reply.id)?;
+    PENDING_INSTANTIATIONS.remove(deps.storage, reply.id);
 
-    let mut found_symbol = String::new();
-    COLLECTIONS.range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .find(|item| {
-            if let Ok((symbol, collection)) = item {
-                if collection.contract_address.as_str().is_empty() {
-                    found_symbol = symbol.to_string();
-                    true
-                } else {
-                    false
-                }
-            } else {
-                false
-            }
-        });
+    let res = parse_reply_instantiate_data(reply)?;
+    let contract_addr = deps.api.addr_validate(&res.contract_address)?;
 
-    if found_symbol.is_empty() {
-        return Err(ContractError::CollectionNotFound {});
+    // The registry entry already carries the predicted address
+    let collection = collections()
+        .may_load(deps.storage, symbol.clone())?
+        .ok_or(ContractError::CollectionNotFound {})?;
+    if collection.contract_address != contract_addr {
+        return Err(ContractError::InvalidInstantiation {});
     }
-
-    COLLECTIONS.update(deps.storage, found_symbol.clone(), |col| match col {
-        Some(mut collection) => {
-            collection.contract_address = contract_addr.clone();
-            Ok(collection)
-        }
-        None => Err(ContractError::CollectionNotFound {}),
-    })?;
+    // Kept until now so a failed instantiation leaves the reservation intact
+    SYMBOL_RESERVATIONS.remove(deps.storage, symbol.clone());
 
     Ok(Response::new()
         .add_attribute("action", "collection_created")
-        .add_attribute("symbol", found_symbol)
+        .add_attribute("symbol", symbol)
         .add_attribute("contract_address", contract_addr.to_string()))
 }
 
@@ -158,10 +640,7 @@ pub fn update_royalties(
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
     
-    // Only admin can update royalties
-    if info.sender != config.admin {
-        return Err(ContractError::Unauthorized {});
-    }
+    ensure_role(deps.storage, &config, &info.sender, Role::Treasury)?;
 
     // Validate percentages
     if house_percentage + artist_percentage != 100 {
@@ -178,3 +657,207 @@ pub fn update_royalties(
         .add_attribute("house_percentage", house_percentage.to_string())
         .add_attribute("artist_percentage", artist_percentage.to_string()))
 }
+
+pub fn migrate_collections(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_code_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_role(deps.storage, &config, &info.sender, Role::CodeManager)?;
+
+    let start = start_after.map(Bound::exclusive);
+    let entries = collections()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(page_limit(limit))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut sub_msgs = Vec::with_capacity(entries.len());
+    for (symbol, collection) in &entries {
+        let reply_id = next_reply_id(deps.storage)?;
+        PENDING_MIGRATIONS.save(deps.storage, reply_id, symbol)?;
+
+        sub_msgs.push(SubMsg::reply_always(
+            WasmMsg::Migrate {
+                contract_addr: collection.contract_address.to_string(),
+                new_code_id,
+                msg: msg.clone(),
+            },
+            reply_id,
+        ));
+    }
+
+    let last_symbol = entries
+        .last()
+        .map(|(symbol, _)| symbol.clone())
+        .unwrap_or_default();
+
+    Ok(Response::new()
+        .add_submessages(sub_msgs)
+        .add_attribute("action", "migrate_collections")
+        .add_attribute("new_code_id", new_code_id.to_string())
+        .add_attribute("count", entries.len().to_string())
+        .add_attribute("last_symbol", last_symbol))
+}
+
+pub fn reply_collection_migrated(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let symbol = PENDING_MIGRATIONS.load(deps.storage, reply.id)?;
+    PENDING_MIGRATIONS.remove(deps.storage, reply.id);
+
+    let collection = collections()
+        .may_load(deps.storage, symbol.clone())?
+        .ok_or(ContractError::CollectionNotFound {})?;
+
+    // Record the code id the chain reports, whether or not the migration went through
+    let code_id = deps
+        .querier
+        .query_wasm_contract_info(collection.contract_address.to_string())?
+        .code_id;
+    let error = match reply.result {
+        SubMsgResult::Ok(_) => None,
+        SubMsgResult::Err(err) => Some(err),
+    };
+
+    let status = MigrationStatus {
+        code_id,
+        success: error.is_none(),
+        error,
+        updated_at: env.block.time.seconds(),
+    };
+    MIGRATION_STATUS.save(deps.storage, symbol.clone(), &status)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "collection_migrated")
+        .add_attribute("symbol", symbol)
+        .add_attribute("code_id", code_id.to_string())
+        .add_attribute("success", status.success.to_string()))
+}
+
+pub fn propose_new_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    let new_admin = deps.api.addr_validate(&new_admin)?;
+    if expires.is_some_and(|e| e.is_expired(&env.block)) {
+        return Err(ContractError::AdminProposalExpired {});
+    }
+
+    PENDING_ADMIN.save(deps.storage, &PendingAdmin {
+        new_admin: new_admin.clone(),
+        expires,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_admin")
+        .add_attribute("new_admin", new_admin)
+        .add_attribute("expires", expires.map_or("none".to_string(), |e| e.to_string())))
+}
+
+pub fn accept_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingAdmin {})?;
+
+    if info.sender != pending.new_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+    if pending.expires.is_some_and(|e| e.is_expired(&env.block)) {
+        return Err(ContractError::AdminProposalExpired {});
+    }
+
+    // Collections keep the factory as wasm admin, so control over them
+    // moves along with the factory admin
+    let mut config = CONFIG.load(deps.storage)?;
+    let previous_admin = config.admin.replace(pending.new_admin.clone());
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", previous_admin.map_or("none".to_string(), |a| a.to_string()))
+        .add_attribute("new_admin", pending.new_admin))
+}
+
+pub fn cancel_admin_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    if PENDING_ADMIN.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingAdmin {});
+    }
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_admin_proposal"))
+}
+
+pub fn renounce_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    config.admin = None;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "renounce_admin")
+        .add_attribute("previous_admin", info.sender))
+}
+
+pub fn grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.save(deps.storage, (role.as_str(), &address), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    config.ensure_admin(&info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
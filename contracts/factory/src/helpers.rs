@@ -0,0 +1,58 @@
+use cosmwasm_std::{instantiate2_address, Addr, Binary, Deps, Env, StdError, StdResult, Storage};
+
+use crate::error::ContractError;
+use crate::state::SYMBOL_GENERATION;
+
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
+/// Most registry entries a filtered page reads
+pub const MAX_SCAN: u32 = 10 * MAX_LIMIT;
+
+/// Page size for paginated messages and queries, capped at `MAX_LIMIT`.
+pub fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+/// Default pass terms must give passes a lifetime, with a grace period no
+/// longer than the pass itself.
+pub fn validate_pass_terms(duration: u64, grace_period: u64) -> Result<(), ContractError> {
+    if duration == 0 {
+        return Err(ContractError::InvalidPassDuration {});
+    }
+    if grace_period > duration {
+        return Err(ContractError::InvalidGracePeriod {});
+    }
+    Ok(())
+}
+
+/// Instantiate2 salt of a collection. The salt is the raw symbol, followed by
+/// `-<generation>` once the symbol has been released, so clients can derive it
+/// without talking to the factory.
+pub fn collection_salt(storage: &dyn Storage, symbol: &str) -> Result<Binary, ContractError> {
+    let salt = match SYMBOL_GENERATION.may_load(storage, symbol.to_string())? {
+        None | Some(0) => symbol.to_string(),
+        Some(generation) => format!("{}-{}", symbol, generation),
+    };
+    // wasmd accepts salts of 1 to 64 bytes
+    if symbol.is_empty() || salt.len() > 64 {
+        return Err(ContractError::InvalidSymbol {});
+    }
+    Ok(Binary::from(salt.into_bytes()))
+}
+
+/// Computes the address the pass_nft contract for `symbol` will be
+/// instantiated at when the factory creates it with `code_id`.
+pub fn predict_collection_address(
+    deps: Deps,
+    env: &Env,
+    code_id: u64,
+    symbol: &str,
+) -> StdResult<Addr> {
+    let salt = collection_salt(deps.storage, symbol).map_err(|e| StdError::generic_err(e.to_string()))?;
+    let code_info = deps.querier.query_wasm_code_info(code_id)?;
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+
+    let canonical = instantiate2_address(&code_info.checksum, &creator, &salt)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+    deps.api.addr_humanize(&canonical)
+}
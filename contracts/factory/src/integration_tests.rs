@@ -1,8 +1,23 @@
 #[cfg(test)]
 mod tests {
-    use crate::msg::{InstantiateMsg, ExecuteMsg, QueryMsg, CollectionResponse};
-    use cosmwasm_std::{Addr, Empty};
-    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+    use crate::msg::{
+        InstantiateMsg, ExecuteMsg, QueryMsg, CollectionResponse, CollectionsResponse,
+        ConfigResponse, PredictedAddressResponse, CollectionCodeIdsResponse, SyncField, SyncTarget,
+        SymbolPolicy, CollectionDetailResponse, CollectionDetailsResponse, SymbolAvailabilityResponse,
+        ArchivedCollectionsResponse,
+    };
+    use crate::state::CollectionStatus;
+    use pass_nft::state::{RecipientRole, SplitRecipient};
+    use cosmwasm_std::{
+        coin, coins, Addr, CosmosMsg, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+        WasmMsg, to_json_binary,
+    };
+    use cw_multi_test::{
+        App, AppBuilder, BankKeeper, Contract, ContractWrapper, Executor, MockAddressGenerator,
+        MockApiBech32, WasmKeeper, no_init,
+    };
+
+    type TestApp = App<BankKeeper, MockApiBech32>;
 
     // Add NFT contract constructor
     pub fn nft_contract() -> Box<dyn Contract<Empty>> {
@@ -10,6 +25,25 @@ mod tests {
             pass_nft::contract::execute,
             pass_nft::contract::instantiate,
             pass_nft::contract::query,
+        ).with_migrate(pass_nft::contract::migrate);
+        Box::new(contract)
+    }
+
+    fn failing_instantiate(
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: pass_nft::msg::InstantiateMsg,
+    ) -> StdResult<Response> {
+        Err(StdError::generic_err("instantiate failed"))
+    }
+
+    // NFT contract whose instantiation always fails
+    pub fn failing_nft_contract() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            pass_nft::contract::execute,
+            failing_instantiate,
+            pass_nft::contract::query,
         );
         Box::new(contract)
     }
@@ -23,12 +57,17 @@ mod tests {
         Box::new(contract)
     }
 
-    fn setup_contracts() -> (App, Addr, Addr, Addr, Addr) {
-        let mut app = App::default();
-        let admin = Addr::unchecked("admin");
-        let artist = Addr::unchecked("artist");
-        let minter = Addr::unchecked("minter");
-        let payment_addr = Addr::unchecked("payment");
+    fn setup_contracts() -> (TestApp, Addr, Addr, Addr, Addr) {
+        // Bech32 addresses and wasmd-style address generation, so that
+        // Instantiate2 addresses can be predicted like on chain
+        let mut app = AppBuilder::default()
+            .with_api(MockApiBech32::new("xion"))
+            .with_wasm(WasmKeeper::default().with_address_generator(MockAddressGenerator))
+            .build(no_init);
+        let admin = app.api().addr_make("admin");
+        let artist = app.api().addr_make("artist");
+        let minter = app.api().addr_make("minter");
+        let payment_addr = app.api().addr_make("payment");
         let house_percentage: u32 = 30;
         let artist_percentage: u32 = 70;
 
@@ -48,7 +87,10 @@ mod tests {
                     grace_period: 259200,
                     payment_address: payment_addr.clone(),
                     artist_percentage,
-                    house_percentage
+                    house_percentage,
+                    self_service: None,
+                    bounds: None,
+                    validation: None,
                 },
                 &[],
                 "factory",
@@ -73,6 +115,7 @@ fn test_create_collection() {
         artist: artist.clone(),
         minter: minter.clone(),
         collection_info: "Test Collection Metadata".to_string(),
+        overrides: None,
     };
 
     println!("Executing create collection with msg: {:?}", msg);
@@ -118,6 +161,8 @@ fn test_symbol_validation() {
         "Test",      // mixed case
         "TEST ",     // with space
         "TEST-1",    // with special character
+        "",          // empty
+        "TESTTESTTESTTESTTESTTESTTESTTESTT", // longer than 32 characters
     ];
 
     for symbol in invalid_symbols {
@@ -127,6 +172,7 @@ fn test_symbol_validation() {
             artist: artist.clone(),
             minter: minter.clone(),
             collection_info: "Test Collection Metadata".to_string(),
+            overrides: None,
         };
 
         let res = app.execute_contract(
@@ -141,4 +187,952 @@ fn test_symbol_validation() {
     }
 }
 
-}
\ No newline at end of file
+#[test]
+fn test_batched_collection_creation() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+
+    // Two CreateCollection messages in a single transaction
+    let msgs: Vec<CosmosMsg> = ["ALPHA", "BETA"]
+        .iter()
+        .map(|symbol| {
+            WasmMsg::Execute {
+                contract_addr: factory_addr.to_string(),
+                msg: to_json_binary(&ExecuteMsg::CreateCollection {
+                    name: format!("{} Collection", symbol),
+                    symbol: symbol.to_string(),
+                    artist: artist.clone(),
+                    minter: minter.clone(),
+                    collection_info: "Test Collection Metadata".to_string(),
+                    overrides: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()
+        })
+        .collect();
+
+    app.execute_multi(admin.clone(), msgs).unwrap();
+
+    let res: CollectionsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::AllCollections {
+                start_after: None,
+                limit: None,
+                order: None,
+                filter: None,
+                include_inactive: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.collections.len(), 2);
+
+    // Every collection is bound to its own pass_nft contract
+    for collection in &res.collections {
+        assert!(!collection.contract_address.as_str().is_empty());
+        let config: pass_nft::msg::ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection.contract_address,
+                &pass_nft::msg::QueryMsg::Extension {
+                    msg: pass_nft::msg::PassQuery::GetConfig {},
+                },
+            )
+            .unwrap();
+        assert_eq!(config.symbol, collection.symbol);
+    }
+    assert_ne!(res.collections[0].contract_address, res.collections[1].contract_address);
+}
+
+#[test]
+fn test_failed_instantiation_releases_symbol() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+
+    // Point the factory at a code whose instantiation fails
+    let failing_code_id = app.store_code(failing_nft_contract());
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateNftCodeId { code_id: failing_code_id },
+        &[],
+    )
+    .unwrap();
+
+    let err = app
+        .execute_contract(
+            admin.clone(),
+            factory_addr.clone(),
+            &ExecuteMsg::CreateCollection {
+                name: "Test Collection".to_string(),
+                symbol: "TEST".to_string(),
+                artist: artist.clone(),
+                minter: minter.clone(),
+                collection_info: "Test Collection Metadata".to_string(),
+                overrides: None,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert!(err.root_cause().to_string().contains("instantiate"));
+
+    let availability: SymbolAvailabilityResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::IsSymbolAvailable { symbol: "TEST".to_string() })
+        .unwrap();
+    assert!(availability.available);
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.total_collections, 0);
+}
+
+#[test]
+fn test_predict_collection_address() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+
+    let predicted: PredictedAddressResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::PredictCollectionAddress { symbol: "TEST".to_string() },
+        )
+        .unwrap();
+
+    let res = app
+        .execute_contract(
+            admin.clone(),
+            factory_addr.clone(),
+            &ExecuteMsg::CreateCollection {
+                name: "Test Collection".to_string(),
+                symbol: "TEST".to_string(),
+                artist: artist.clone(),
+                minter: minter.clone(),
+                collection_info: "Test Collection Metadata".to_string(),
+                overrides: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+    // The create_collection event already carries the final address
+    let emitted = res
+        .events
+        .iter()
+        .flat_map(|e| &e.attributes)
+        .find(|attr| attr.key == "contract_address")
+        .map(|attr| attr.value.clone())
+        .unwrap();
+    assert_eq!(emitted, predicted.contract_address.to_string());
+
+    let query_res: CollectionResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::Collection { artist: artist.to_string() })
+        .unwrap();
+    assert_eq!(query_res.collection.unwrap().contract_address, predicted.contract_address);
+
+    // The pass_nft contract really lives at the predicted address
+    let config: pass_nft::msg::ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &predicted.contract_address,
+            &pass_nft::msg::QueryMsg::Extension {
+                msg: pass_nft::msg::PassQuery::GetConfig {},
+            },
+        )
+        .unwrap();
+    assert_eq!(config.symbol, "TEST");
+}
+
+fn create_collection(app: &mut TestApp, factory_addr: &Addr, admin: &Addr, symbol: &str) {
+    let artist = app.api().addr_make("artist");
+    let minter = app.api().addr_make("minter");
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::CreateCollection {
+            name: format!("{} Collection", symbol),
+            symbol: symbol.to_string(),
+            artist,
+            minter,
+            collection_info: "Test Collection Metadata".to_string(),
+            overrides: None,
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_migrate_collections() {
+    let (mut app, factory_addr, admin, artist, _minter) = setup_contracts();
+    create_collection(&mut app, &factory_addr, &admin, "ALPHA");
+    create_collection(&mut app, &factory_addr, &admin, "BETA");
+
+    // Ship a new pass_nft code and make it the factory default
+    let new_code_id = app.store_code(nft_contract());
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateNftCodeId { code_id: new_code_id },
+        &[],
+    )
+    .unwrap();
+
+    let report: CollectionCodeIdsResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::CollectionCodeIds { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(report.nft_code_id, new_code_id);
+    assert!(report.collections.iter().all(|c| !c.up_to_date && c.last_migration.is_none()));
+
+    let migrate_msg = ExecuteMsg::MigrateCollections {
+        new_code_id,
+        start_after: None,
+        limit: Some(1),
+        msg: to_json_binary(&pass_nft::msg::MigrateMsg {}).unwrap(),
+    };
+
+    // Only the factory admin can trigger migrations
+    let err = app
+        .execute_contract(artist.clone(), factory_addr.clone(), &migrate_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<crate::ContractError>().unwrap(),
+        crate::ContractError::MissingRole { role: crate::state::Role::CodeManager }
+    );
+
+    // First page
+    app.execute_contract(admin.clone(), factory_addr.clone(), &migrate_msg, &[]).unwrap();
+    let report: CollectionCodeIdsResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::CollectionCodeIds { start_after: None, limit: None })
+        .unwrap();
+    assert!(report.collections[0].up_to_date);
+    assert!(report.collections[0].last_migration.as_ref().unwrap().success);
+    assert!(!report.collections[1].up_to_date);
+
+    // Second page
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::MigrateCollections {
+            new_code_id,
+            start_after: Some("ALPHA".to_string()),
+            limit: Some(1),
+            msg: to_json_binary(&pass_nft::msg::MigrateMsg {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+    let report: CollectionCodeIdsResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::CollectionCodeIds { start_after: None, limit: None })
+        .unwrap();
+    assert!(report.collections.iter().all(|c| c.up_to_date));
+}
+
+#[test]
+fn test_failed_collection_migration_is_recorded() {
+    let (mut app, factory_addr, admin, _artist, _minter) = setup_contracts();
+    create_collection(&mut app, &factory_addr, &admin, "ALPHA");
+
+    // This code has no migrate entry point
+    let broken_code_id = app.store_code(failing_nft_contract());
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::MigrateCollections {
+            new_code_id: broken_code_id,
+            start_after: None,
+            limit: None,
+            msg: to_json_binary(&pass_nft::msg::MigrateMsg {}).unwrap(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let report: CollectionCodeIdsResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::CollectionCodeIds { start_after: None, limit: None })
+        .unwrap();
+    let status = report.collections[0].last_migration.as_ref().unwrap();
+    assert!(!status.success);
+    assert!(status.error.is_some());
+    assert_eq!(status.code_id, report.nft_code_id);
+}
+
+#[test]
+fn test_self_service_creation_fee() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+    app.init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &artist, coins(1000, "uxion")).unwrap();
+    });
+
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateSelfService {
+            config: crate::state::SelfServiceConfig {
+                enabled: true,
+                creation_fee: Some(coin(250, "uxion")),
+                list_mode: crate::state::ArtistListMode::Disabled,
+                max_collections_per_artist: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        artist.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::CreateCollection {
+            name: "Test Collection".to_string(),
+            symbol: "TEST".to_string(),
+            artist: artist.clone(),
+            minter: minter.clone(),
+            collection_info: "Test Collection Metadata".to_string(),
+            overrides: None,
+        },
+        &coins(250, "uxion"),
+    )
+    .unwrap();
+
+    let payment_addr = app.api().addr_make("payment");
+    assert_eq!(app.wrap().query_balance(&payment_addr, "uxion").unwrap().amount.u128(), 250);
+    assert_eq!(app.wrap().query_balance(&artist, "uxion").unwrap().amount.u128(), 750);
+    assert_eq!(app.wrap().query_balance(&factory_addr, "uxion").unwrap().amount.u128(), 0);
+
+    let res: CollectionResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::Collection { artist: artist.to_string() })
+        .unwrap();
+    assert_eq!(res.collection.unwrap().symbol, "TEST");
+}
+
+#[test]
+fn test_failed_self_service_creation_keeps_fee() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+    app.init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &artist, coins(1000, "uxion")).unwrap();
+    });
+
+    let failing_code_id = app.store_code(failing_nft_contract());
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateNftCodeId { code_id: failing_code_id },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateSelfService {
+            config: crate::state::SelfServiceConfig {
+                enabled: true,
+                creation_fee: Some(coin(250, "uxion")),
+                list_mode: crate::state::ArtistListMode::Disabled,
+                max_collections_per_artist: None,
+            },
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        artist.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::CreateCollection {
+            name: "Test Collection".to_string(),
+            symbol: "TEST".to_string(),
+            artist: artist.clone(),
+            minter: minter.clone(),
+            collection_info: "Test Collection Metadata".to_string(),
+            overrides: None,
+        },
+        &coins(250, "uxion"),
+    )
+    .unwrap_err();
+
+    // The fee goes back with the rest of the transaction
+    let payment_addr = app.api().addr_make("payment");
+    assert_eq!(app.wrap().query_balance(&artist, "uxion").unwrap().amount.u128(), 1000);
+    assert_eq!(app.wrap().query_balance(&payment_addr, "uxion").unwrap().amount.u128(), 0);
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.total_collections, 0);
+}
+
+#[test]
+fn test_collection_overrides_reach_pass_nft() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::CreateCollection {
+            name: "Headline Collection".to_string(),
+            symbol: "HEAD".to_string(),
+            artist: artist.clone(),
+            minter: minter.clone(),
+            collection_info: "Test Collection Metadata".to_string(),
+            overrides: Some(crate::state::CollectionOverrides {
+                price: Some(1000),
+                duration: Some(31536000),
+                ..Default::default()
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+    create_collection(&mut app, &factory_addr, &admin, "INDIE");
+
+    let res: CollectionsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::AllCollections {
+                start_after: None,
+                limit: None,
+                order: None,
+                filter: None,
+                include_inactive: None,
+            },
+        )
+        .unwrap();
+    let head = res.collections.iter().find(|c| c.symbol == "HEAD").unwrap();
+    let indie = res.collections.iter().find(|c| c.symbol == "INDIE").unwrap();
+    assert_eq!((head.pass_price, head.pass_duration), (1000, 31536000));
+    assert_eq!((indie.pass_price, indie.pass_duration), (100, 2592000));
+
+    let config: pass_nft::msg::ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &head.contract_address,
+            &pass_nft::msg::QueryMsg::Extension {
+                msg: pass_nft::msg::PassQuery::GetConfig {},
+            },
+        )
+        .unwrap();
+    assert_eq!(config.pass_price, 1000);
+    assert_eq!(config.pass_duration, 31536000);
+    assert_eq!(config.pass_denom, "uxion");
+}
+
+#[test]
+fn test_sync_collections() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+    create_collection(&mut app, &factory_addr, &admin, "ALPHA");
+    create_collection(&mut app, &factory_addr, &admin, "BETA");
+
+    let treasury = app.api().addr_make("treasury");
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateConfig {
+            price: Some(500),
+            duration: None,
+            grace_period: None,
+            payment_address: Some(treasury.to_string()),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateRoyalties { house_percentage: 20, artist_percentage: 80 },
+        &[],
+    )
+    .unwrap();
+
+    let sync = ExecuteMsg::SyncCollections {
+        target: SyncTarget::All { start_after: None, limit: Some(1) },
+        fields: vec![SyncField::PaymentAddress, SyncField::Royalties],
+    };
+    let err = app
+        .execute_contract(artist.clone(), factory_addr.clone(), &sync, &[])
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<crate::ContractError>().unwrap(),
+        crate::ContractError::MissingRole { role: crate::state::Role::Treasury }
+    );
+
+    // One page at a time
+    app.execute_contract(admin.clone(), factory_addr.clone(), &sync, &[]).unwrap();
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::SyncCollections {
+            target: SyncTarget::Symbols { symbols: vec!["BETA".to_string()] },
+            fields: vec![SyncField::PaymentAddress, SyncField::Royalties],
+        },
+        &[],
+    )
+    .unwrap();
+
+    let res: CollectionsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::AllCollections {
+                start_after: None,
+                limit: None,
+                order: None,
+                filter: None,
+                include_inactive: None,
+            },
+        )
+        .unwrap();
+    for collection in &res.collections {
+        assert_eq!((collection.house_percentage, collection.artist_percentage), (20, 80));
+        // Price was not part of the sync
+        assert_eq!(collection.pass_price, 100);
+
+        let config: pass_nft::msg::ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection.contract_address,
+                &pass_nft::msg::QueryMsg::Extension {
+                    msg: pass_nft::msg::PassQuery::GetConfig {},
+                },
+            )
+            .unwrap();
+        assert_eq!(config.payment_address, treasury);
+        assert_eq!((config.house_percentage, config.artist_percentage), (20, 80));
+        assert_eq!(config.pass_price, 100);
+    }
+
+    // Only the factory may redirect a collection's revenue, not even its minter
+    for sender in [&artist, &minter] {
+        let err = app
+            .execute_contract(
+                sender.clone(),
+                res.collections[0].contract_address.clone(),
+                &pass_nft::msg::ExecuteMsg::Extension {
+                    msg: pass_nft::msg::PassMsg::UpdateConfig(pass_nft::msg::ConfigUpdate {
+                        payment_address: Some(artist.to_string()),
+                        ..Default::default()
+                    }),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<pass_nft::ContractError>().unwrap(),
+            pass_nft::ContractError::Unauthorized {}
+        );
+    }
+}
+
+#[test]
+fn test_sync_royalties_keeps_custom_splits() {
+    let (mut app, factory_addr, admin, _artist, _minter) = setup_contracts();
+    create_collection(&mut app, &factory_addr, &admin, "ALPHA");
+    create_collection(&mut app, &factory_addr, &admin, "BETA");
+    let collection_address = |app: &TestApp, symbol: &str| {
+        let res: CollectionDetailResponse = app
+            .wrap()
+            .query_wasm_smart(&factory_addr, &QueryMsg::CollectionBySymbol { symbol: symbol.to_string() })
+            .unwrap();
+        res.collection.unwrap().collection.contract_address
+    };
+
+    // BETA pays a producer alongside the artist and the house
+    let producer = app.api().addr_make("producer");
+    let split = vec![
+        SplitRecipient { role: RecipientRole::Artist, address: None, bps: 6000 },
+        SplitRecipient { role: RecipientRole::House, address: None, bps: 3000 },
+        SplitRecipient { role: RecipientRole::Producer, address: Some(producer), bps: 1000 },
+    ];
+    app.execute_contract(
+        factory_addr.clone(),
+        collection_address(&app, "BETA"),
+        &pass_nft::msg::ExecuteMsg::Extension {
+            msg: pass_nft::msg::PassMsg::UpdateConfig(pass_nft::msg::ConfigUpdate {
+                revenue_split: Some(split.clone()),
+                ..Default::default()
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateRoyalties { house_percentage: 20, artist_percentage: 80 },
+        &[],
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            admin.clone(),
+            factory_addr.clone(),
+            &ExecuteMsg::SyncCollections {
+                target: SyncTarget::All { start_after: None, limit: None },
+                fields: vec![SyncField::Royalties],
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(res.events.iter().flat_map(|e| &e.attributes).any(|attr| attr.key == "skipped" && attr.value == "BETA"));
+
+    let nft_config = |app: &TestApp, symbol: &str| -> pass_nft::msg::ConfigResponse {
+        app.wrap()
+            .query_wasm_smart(
+                collection_address(app, symbol),
+                &pass_nft::msg::QueryMsg::Extension { msg: pass_nft::msg::PassQuery::GetConfig {} },
+            )
+            .unwrap()
+    };
+    let alpha = nft_config(&app, "ALPHA");
+    assert_eq!((alpha.house_percentage, alpha.artist_percentage), (20, 80));
+    assert_eq!(nft_config(&app, "BETA").revenue_split, split);
+}
+
+#[test]
+fn test_sync_price_skips_other_denoms() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+    create_collection(&mut app, &factory_addr, &admin, "ALPHA");
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateCollectionBounds {
+            bounds: crate::state::CollectionBounds {
+                accepted_denoms: vec!["uusdc".to_string()],
+                ..Default::default()
+            },
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::CreateCollection {
+            name: "Stable Collection".to_string(),
+            symbol: "STABLE".to_string(),
+            artist: artist.clone(),
+            minter: minter.clone(),
+            collection_info: "Test Collection Metadata".to_string(),
+            overrides: Some(crate::state::CollectionOverrides {
+                price: Some(7),
+                denom: Some("uusdc".to_string()),
+                ..Default::default()
+            }),
+        },
+        &[],
+    )
+    .unwrap();
+
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::UpdateConfig { price: Some(500), duration: None, grace_period: None, payment_address: None },
+        &[],
+    )
+    .unwrap();
+    let res = app
+        .execute_contract(
+            admin.clone(),
+            factory_addr.clone(),
+            &ExecuteMsg::SyncCollections {
+                target: SyncTarget::All { start_after: None, limit: None },
+                fields: vec![SyncField::Price],
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(res.events.iter().flat_map(|e| &e.attributes).any(|attr| attr.key == "skipped" && attr.value == "STABLE"));
+
+    // The uxion price only reaches the collection priced in uxion
+    let res: CollectionsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::AllCollections { start_after: None, limit: None, order: None, filter: None, include_inactive: None },
+        )
+        .unwrap();
+    for collection in &res.collections {
+        let config: pass_nft::msg::ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &collection.contract_address,
+                &pass_nft::msg::QueryMsg::Extension { msg: pass_nft::msg::PassQuery::GetConfig {} },
+            )
+            .unwrap();
+        let expected = if collection.symbol == "STABLE" { (7, "uusdc") } else { (500, "uxion") };
+        assert_eq!((collection.pass_price, collection.pass_denom.as_str()), expected);
+        assert_eq!((config.pass_price, config.pass_denom.as_str()), expected);
+    }
+}
+
+#[test]
+fn test_collection_lifecycle() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+    create_collection(&mut app, &factory_addr, &admin, "ALPHA");
+    create_collection(&mut app, &factory_addr, &admin, "BETA");
+    app.init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &artist, coins(1000, "uxion")).unwrap();
+    });
+
+    let collection = |app: &TestApp, symbol: &str| {
+        let res: CollectionsResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &factory_addr,
+                &QueryMsg::AllCollections {
+                    start_after: None,
+                    limit: None,
+                    order: None,
+                    filter: None,
+                    include_inactive: Some(true),
+                },
+            )
+            .unwrap();
+        res.collections.into_iter().find(|c| c.symbol == symbol)
+    };
+    let alpha = collection(&app, "ALPHA").unwrap();
+    let mint = pass_nft::msg::ExecuteMsg::Extension {
+        msg: pass_nft::msg::PassMsg::MintPass { owner_address: artist.to_string(), tier: None },
+    };
+
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::DeactivateCollection { symbol: "ALPHA".to_string() },
+        &[],
+    )
+    .unwrap();
+
+    // Hidden by default and paused on the pass_nft side
+    let res: CollectionsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::AllCollections {
+                start_after: None,
+                limit: None,
+                order: None,
+                filter: None,
+                include_inactive: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.collections.len(), 1);
+    assert_eq!(collection(&app, "ALPHA").unwrap().status, CollectionStatus::Inactive);
+    let err = app
+        .execute_contract(artist.clone(), alpha.contract_address.clone(), &mint, &coins(100, "uxion"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<pass_nft::ContractError>().unwrap(),
+        pass_nft::ContractError::CollectionPaused {}
+    );
+
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::ReactivateCollection { symbol: "ALPHA".to_string() },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(artist.clone(), alpha.contract_address.clone(), &mint, &coins(100, "uxion"))
+        .unwrap();
+
+    // Retired symbols stay taken
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::ArchiveCollection { symbol: "BETA".to_string(), symbol_policy: SymbolPolicy::Retire },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(collection(&app, "BETA").unwrap().status, CollectionStatus::Archived);
+    let err = app
+        .execute_contract(
+            admin.clone(),
+            factory_addr.clone(),
+            &ExecuteMsg::ReactivateCollection { symbol: "BETA".to_string() },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.downcast::<crate::ContractError>().unwrap(), crate::ContractError::CollectionArchived {});
+    let availability: SymbolAvailabilityResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::IsSymbolAvailable { symbol: "BETA".to_string() })
+        .unwrap();
+    assert!(!availability.available);
+
+    // Released symbols can back a new collection, at a new address
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::ArchiveCollection { symbol: "ALPHA".to_string(), symbol_policy: SymbolPolicy::Release },
+        &[],
+    )
+    .unwrap();
+    assert!(collection(&app, "ALPHA").is_none());
+
+    // The released collection can still be looked up
+    let by_address: CollectionResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::CollectionByAddress { contract_address: alpha.contract_address.to_string() },
+        )
+        .unwrap();
+    assert_eq!(by_address.collection.unwrap().status, CollectionStatus::Archived);
+    let archived: ArchivedCollectionsResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::ArchivedCollections { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(archived.collections.len(), 1);
+    assert_eq!(archived.collections[0].generation, 0);
+    assert_eq!(archived.collections[0].collection.contract_address, alpha.contract_address);
+    assert!(archived.next.is_none());
+    let err = app
+        .execute_contract(artist.clone(), alpha.contract_address.clone(), &mint, &coins(100, "uxion"))
+        .unwrap_err();
+    assert_eq!(
+        err.downcast::<pass_nft::ContractError>().unwrap(),
+        pass_nft::ContractError::CollectionPaused {}
+    );
+
+    let predicted: PredictedAddressResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::PredictCollectionAddress { symbol: "ALPHA".to_string() })
+        .unwrap();
+    assert_ne!(predicted.contract_address, alpha.contract_address);
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::CreateCollection {
+            name: "New Alpha".to_string(),
+            symbol: "ALPHA".to_string(),
+            artist: artist.clone(),
+            minter,
+            collection_info: "Test Collection Metadata".to_string(),
+            overrides: None,
+        },
+        &[],
+    )
+    .unwrap();
+    let new_alpha = collection(&app, "ALPHA").unwrap();
+    assert_eq!(new_alpha.contract_address, predicted.contract_address);
+    assert_eq!(new_alpha.status, CollectionStatus::Active);
+}
+
+#[test]
+fn test_collection_lookups_with_stats() {
+    let (mut app, factory_addr, admin, artist, minter) = setup_contracts();
+    create_collection(&mut app, &factory_addr, &admin, "ALPHA");
+    create_collection(&mut app, &factory_addr, &admin, "BETA");
+    app.init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &artist, coins(1000, "uxion")).unwrap();
+    });
+
+    let res: CollectionDetailResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::CollectionBySymbol { symbol: "BETA".to_string() })
+        .unwrap();
+    let beta = res.collection.unwrap();
+    assert_eq!(beta.collection.symbol, "BETA");
+    assert_eq!(beta.stats.total_passes, 0);
+
+    app.execute_contract(
+        artist.clone(),
+        beta.collection.contract_address.clone(),
+        &pass_nft::msg::ExecuteMsg::Extension {
+            msg: pass_nft::msg::PassMsg::MintPass { owner_address: artist.to_string(), tier: None },
+        },
+        &coins(100, "uxion"),
+    )
+    .unwrap();
+
+    // Stats are read from the pass_nft on every query
+    let res: CollectionDetailsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::Collections {
+                symbols: vec!["BETA".to_string(), "MISSING".to_string(), "ALPHA".to_string()],
+            },
+        )
+        .unwrap();
+    let symbols: Vec<_> = res.collections.iter().map(|c| c.collection.symbol.as_str()).collect();
+    assert_eq!(symbols, vec!["BETA", "ALPHA"]);
+    let stats = &res.collections[0].stats;
+    assert_eq!((stats.total_passes, stats.active_passes), (1, 1));
+    assert_eq!(res.collections[1].stats.total_passes, 0);
+
+    // Past its expiry the pass is still held but no longer active
+    app.update_block(|block| block.time = block.time.plus_seconds(2592000));
+    let res: CollectionDetailResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::CollectionBySymbol { symbol: "BETA".to_string() })
+        .unwrap();
+    let stats = res.collection.unwrap().stats;
+    assert_eq!((stats.total_passes, stats.active_passes), (1, 0));
+
+    let res: CollectionDetailsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::CollectionsByMinter { minter: minter.to_string(), start_after: None, limit: Some(1) },
+        )
+        .unwrap();
+    assert_eq!(res.collections[0].collection.symbol, "ALPHA");
+    assert_eq!(res.next, Some("ALPHA".to_string()));
+    let res: CollectionDetailsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory_addr,
+            &QueryMsg::CollectionsByMinter { minter: minter.to_string(), start_after: res.next, limit: Some(1) },
+        )
+        .unwrap();
+    assert_eq!(res.collections[0].collection.symbol, "BETA");
+}
+
+#[test]
+fn test_reserved_symbol_launch() {
+    let (mut app, factory_addr, admin, artist, _minter) = setup_contracts();
+    app.execute_contract(
+        admin.clone(),
+        factory_addr.clone(),
+        &ExecuteMsg::ReserveSymbol {
+            symbol: "DROP".to_string(),
+            artist: artist.to_string(),
+            expires: cw_utils::Expiration::Never {},
+        },
+        &[],
+    )
+    .unwrap();
+    create_collection(&mut app, &factory_addr, &admin, "DROP");
+
+    // The reservation is consumed once the collection exists
+    let availability: SymbolAvailabilityResponse = app
+        .wrap()
+        .query_wasm_smart(&factory_addr, &QueryMsg::IsSymbolAvailable { symbol: "DROP".to_string() })
+        .unwrap();
+    assert!(availability.taken);
+    assert_eq!(availability.reserved_by, None);
+    let err = app
+        .execute_contract(
+            admin.clone(),
+            factory_addr.clone(),
+            &ExecuteMsg::ReleaseReservation { symbol: "DROP".to_string() },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err.downcast::<crate::ContractError>().unwrap(), crate::ContractError::ReservationNotFound {});
+}
+}
@@ -1,6 +1,6 @@
 pub mod contract;
 mod error;
-// pub mod helpers;
+pub mod helpers;
 pub mod integration_tests;
 pub mod unit_test;
 pub mod msg;
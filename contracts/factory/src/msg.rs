@@ -1,6 +1,10 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Addr;
-use crate::state::Collection;
+use cosmwasm_std::{Addr, Binary, Order};
+use cw_utils::Expiration;
+use crate::state::{
+    Collection, CollectionBounds, CollectionOverrides, CollectionStatus, MigrationStatus, Role, SelfServiceConfig,
+    ValidationPolicy,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -12,6 +16,9 @@ pub struct InstantiateMsg {
 
     pub house_percentage: u32,
     pub artist_percentage: u32,
+    pub self_service: Option<SelfServiceConfig>,
+    pub bounds: Option<CollectionBounds>,
+    pub validation: Option<ValidationPolicy>,
 }
 
 #[cw_serde]
@@ -22,6 +29,8 @@ pub enum ExecuteMsg {
         artist: Addr,
         minter: Addr,
         collection_info: String,
+        /// Pass terms replacing the factory defaults, within the factory bounds
+        overrides: Option<CollectionOverrides>,
     },
     UpdateNftCodeId {
         code_id: u64,
@@ -31,45 +40,277 @@ pub enum ExecuteMsg {
         house_percentage: u32,
         artist_percentage: u32,
     },
+
+    /// Starts a two-step admin transfer. The proposal replaces any
+    /// earlier one and can optionally expire.
+    ProposeNewAdmin {
+        new_admin: String,
+        expires: Option<Expiration>,
+    },
+
+    /// Called by the proposed admin to complete the transfer
+    AcceptAdmin {},
+
+    CancelAdminProposal {},
+
+    /// Leaves the factory without an admin. This cannot be undone.
+    RenounceAdmin {},
+
+    /// Updates the default pass terms and payment address. Omitted fields
+    /// are left unchanged.
+    UpdateConfig {
+        price: Option<u128>,
+        duration: Option<u64>,
+        grace_period: Option<u64>,
+        payment_address: Option<String>,
+    },
+
+    /// Pushes the current factory values of `fields` to existing collections
+    SyncCollections {
+        target: SyncTarget,
+        fields: Vec<SyncField>,
+    },
+
+    /// Hides a collection and pauses minting and renewals on its pass_nft
+    DeactivateCollection { symbol: String },
+
+    ReactivateCollection { symbol: String },
+
+    /// Permanently retires a collection. `symbol_policy` decides whether the
+    /// symbol can be used by a new collection.
+    ArchiveCollection {
+        symbol: String,
+        symbol_policy: SymbolPolicy,
+    },
+
+    UpdateCollectionBounds {
+        bounds: CollectionBounds,
+    },
+
+    UpdateValidationPolicy {
+        policy: ValidationPolicy,
+    },
+
+    /// Holds `symbol` for `artist` until `expires`. Only a collection for
+    /// that artist can be created with it in the meantime.
+    ReserveSymbol {
+        symbol: String,
+        artist: String,
+        expires: Expiration,
+    },
+
+    /// Drops a reservation. Callable by the admin or the reserving artist.
+    ReleaseReservation {
+        symbol: String,
+    },
+
+    UpdateSelfService {
+        config: SelfServiceConfig,
+    },
+
+    /// Adds or removes artists from the self-service allow/deny list
+    UpdateArtistList {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+
+    /// Migrates a page of child pass collections to `new_code_id`.
+    /// `msg` is passed through as the pass_nft `MigrateMsg`.
+    MigrateCollections {
+        new_code_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        msg: Binary,
+    },
 }
 
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Collections a `SyncCollections` call applies to
+#[cw_serde]
+pub enum SyncTarget {
+    Symbols { symbols: Vec<String> },
+    All { start_after: Option<String>, limit: Option<u32> },
+}
+
+/// What happens to the symbol of an archived collection
+#[cw_serde]
+pub enum SymbolPolicy {
+    /// The symbol stays taken for good
+    Retire,
+    /// The symbol becomes available and the collection leaves the registry
+    Release,
+}
+
+/// Factory settings that can be pushed to existing collections
+#[cw_serde]
+#[derive(Copy)]
+pub enum SyncField {
+    PaymentAddress,
+    /// Skipped for collections with a custom revenue split
+    Royalties,
+    /// Skipped for collections priced in another denom than uxion
+    Price,
+    Duration,
+    GracePeriod,
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
     
+    #[returns(PendingAdminResponse)]
+    PendingAdmin {},
+
+    #[returns(RolesResponse)]
+    Roles {
+        address: String,
+    },
+
+    #[returns(RoleMembersResponse)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(ArtistListResponse)]
+    ArtistList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// First collection of `artist` by symbol. Use `ArtistCollections` to
+    /// list all of them.
     #[returns(CollectionResponse)]
     Collection { 
         artist: String 
     },
 
+    #[returns(CollectionDetailResponse)]
+    CollectionBySymbol {
+        symbol: String,
+    },
+
+    #[returns(CollectionDetailsResponse)]
+    CollectionsByMinter {
+        minter: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Registry entries for up to `MAX_LIMIT` symbols, in the order asked.
+    /// Unknown symbols are left out.
+    #[returns(CollectionDetailsResponse)]
+    Collections {
+        symbols: Vec<String>,
+    },
+
+    /// Also finds collections archived with `SymbolPolicy::Release`
+    #[returns(CollectionResponse)]
+    CollectionByAddress {
+        contract_address: String,
+    },
+
     #[returns(CollectionsResponse)]
     ArtistCollections { 
         artist: String,
+        start_after: Option<String>,
         limit: Option<u32>,
+        order: Option<SortOrder>,
+        filter: Option<CollectionFilter>,
     },
 
     #[returns(CollectionsResponse)]
+    /// Only active collections unless `include_inactive` is set or the
+    /// filter asks for a status
     AllCollections {
+        start_after: Option<String>,
         limit: Option<u32>,
+        order: Option<SortOrder>,
+        filter: Option<CollectionFilter>,
+        include_inactive: Option<bool>,
     },
 
-    #[returns(bool)]
+    #[returns(SymbolAvailabilityResponse)]
     IsSymbolAvailable { 
         symbol: String 
     },
+
+    #[returns(PredictedAddressResponse)]
+    PredictCollectionAddress {
+        symbol: String,
+    },
+
+    #[returns(CollectionCodeIdsResponse)]
+    CollectionCodeIds {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Collections archived with `SymbolPolicy::Release`, which no longer
+    /// show up in the registry queries, by symbol and generation
+    #[returns(ArchivedCollectionsResponse)]
+    ArchivedCollections {
+        start_after: Option<(String, u32)>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
     pub nft_code_id: u64,
-    pub admin: String,
+    pub admin: Option<String>,
     pub total_collections: u64,
 
+    pub price: u128,
+    pub duration: u64,
+    pub grace_period: u64,
+    pub payment_address: Addr,
+
     pub house_percentage: u32,
     pub artist_percentage: u32,
+    pub self_service: SelfServiceConfig,
+    pub bounds: CollectionBounds,
+    pub validation: ValidationPolicy,
+}
+
+#[cw_serde]
+pub struct ArtistListResponse {
+    pub artists: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct PendingAdminResponse {
+    pub pending_admin: Option<Addr>,
+    pub expires: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub address: Addr,
+    pub is_admin: bool,
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub role: Role,
+    pub members: Vec<Addr>,
 }
 
 #[cw_serde]
@@ -80,6 +321,116 @@ pub struct CollectionResponse {
 #[cw_serde]
 pub struct CollectionsResponse {
     pub collections: Vec<Collection>,
+    /// Pass as `start_after` to get the next page, `None` on the last page.
+    /// A filtered page can be short, or empty, and still have a next page.
+    pub next: Option<String>,
+}
+
+/// Pass counts read from the pass_nft contract at query time
+#[cw_serde]
+pub struct CollectionStats {
+    pub total_passes: u64,
+    /// Passes before their expiry, passes in their grace period are not counted
+    pub active_passes: u64,
+}
+
+#[cw_serde]
+pub struct CollectionDetails {
+    pub collection: Collection,
+    pub stats: CollectionStats,
+}
+
+#[cw_serde]
+pub struct CollectionDetailResponse {
+    pub collection: Option<CollectionDetails>,
+}
+
+#[cw_serde]
+pub struct CollectionDetailsResponse {
+    pub collections: Vec<CollectionDetails>,
+    /// Pass as `start_after` to get the next page, `None` on the last page
+    pub next: Option<String>,
+}
+
+/// Collections are listed by symbol
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl From<SortOrder> for Order {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Ascending => Order::Ascending,
+            SortOrder::Descending => Order::Descending,
+        }
+    }
+}
+
+/// All set fields must match
+#[cw_serde]
+#[derive(Default)]
+pub struct CollectionFilter {
+    pub artist: Option<String>,
+    pub minter: Option<String>,
+    /// Inclusive lower bound on `created_at`, in seconds
+    pub created_after: Option<u64>,
+    /// Exclusive upper bound on `created_at`, in seconds
+    pub created_before: Option<u64>,
+    pub status: Option<CollectionStatus>,
+}
+
+#[cw_serde]
+pub struct SymbolAvailabilityResponse {
+    pub symbol: String,
+    /// Free for any artist: valid, neither taken nor reserved
+    pub available: bool,
+    /// Passes the symbol length, charset and reserved symbol checks
+    pub valid: bool,
+    /// Used by a collection, or retired
+    pub taken: bool,
+    /// Set while an unexpired reservation holds the symbol
+    pub reserved_by: Option<Addr>,
+    pub reserved_until: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct PredictedAddressResponse {
+    pub symbol: String,
+    pub contract_address: Addr,
+}
+
+#[cw_serde]
+pub struct CollectionCodeIdInfo {
+    pub symbol: String,
+    pub contract_address: Addr,
+    pub code_id: u64,
+    pub up_to_date: bool,
+    pub last_migration: Option<MigrationStatus>,
+}
+
+#[cw_serde]
+pub struct CollectionCodeIdsResponse {
+    pub nft_code_id: u64,
+    pub collections: Vec<CollectionCodeIdInfo>,
+}
+
+#[cw_serde]
+pub struct ArchivedCollection {
+    /// Times the symbol had been released before this collection was created
+    pub generation: u32,
+    pub code_id: u64,
+    pub collection: Collection,
+}
+
+#[cw_serde]
+pub struct ArchivedCollectionsResponse {
+    pub collections: Vec<ArchivedCollection>,
+    /// Pass as `start_after` to get the next page, `None` on the last page
+    pub next: Option<(String, u32)>,
 }
 
 // Events remain the same
@@ -92,11 +443,16 @@ pub struct CollectionCreatedEvent {
     pub contract_address: Addr,
     pub house_percentage: u32,
     pub artist_percentage: u32,
+    pub pass_price: u128,
+    pub pass_denom: String,
+    pub pass_duration: u64,
+    pub grace_period: u64,
 }
 
 #[cw_serde]
 pub struct CollectionUpdatedEvent {
     pub symbol: String, 
     pub is_active: bool,
+    pub status: CollectionStatus,
 }
 
@@ -1,63 +1,400 @@
 
-use cosmwasm_std::{Deps, StdResult};
-use crate::msg::{ConfigResponse, CollectionResponse, CollectionsResponse};
-use crate::state::{CONFIG, COLLECTIONS, COLLECTION_COUNT, ARTIST_COLLECTIONS, SYMBOL_TAKEN, Collection};
+use cosmwasm_std::{Deps, Env, Order, StdError, StdResult};
+use cw_storage_plus::Bound;
+use pass_nft::msg::{ArtistInfoResponse, PassQuery, QueryMsg as NftQueryMsg};
+use crate::helpers::{page_limit, predict_collection_address, MAX_LIMIT, MAX_SCAN};
+use crate::msg::{
+    ConfigResponse, CollectionResponse, CollectionsResponse, PredictedAddressResponse,
+    CollectionCodeIdInfo, CollectionCodeIdsResponse, PendingAdminResponse, RolesResponse,
+    RoleMembersResponse, ArtistListResponse, CollectionFilter, SortOrder, CollectionDetails,
+    CollectionDetailResponse, CollectionDetailsResponse, CollectionStats, SymbolAvailabilityResponse,
+    ArchivedCollection, ArchivedCollectionsResponse,
+};
+use crate::state::{
+    active_reservation, archived_collections, CONFIG, collections, COLLECTION_COUNT, SYMBOL_TAKEN, MIGRATION_STATUS,
+    PENDING_ADMIN, ROLES, ARTIST_LIST, Collection, CollectionStatus, Role,
+};
 
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config = CONFIG.load(deps.storage)?;
     Ok(ConfigResponse {
         nft_code_id: config.nft_code_id,
-        admin: config.admin.to_string(),
+        admin: config.admin.map(|admin| admin.to_string()),
         total_collections: COLLECTION_COUNT.load(deps.storage)?,
+        price: config.price,
+        duration: config.duration,
+        grace_period: config.grace_period,
+        payment_address: config.payment_address,
         house_percentage: config.house_percentage,
-        artist_percentage: config.artist_percentage
+        artist_percentage: config.artist_percentage,
+        self_service: config.self_service,
+        bounds: config.bounds,
+        validation: config.validation,
     })
 }
 
+pub fn query_pending_admin(deps: Deps) -> StdResult<PendingAdminResponse> {
+    let pending = PENDING_ADMIN.may_load(deps.storage)?;
+    Ok(PendingAdminResponse {
+        pending_admin: pending.as_ref().map(|p| p.new_admin.clone()),
+        expires: pending.and_then(|p| p.expires),
+    })
+}
+
+pub fn query_roles(deps: Deps, address: String) -> StdResult<RolesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+
+    let roles = [Role::CollectionCreator, Role::Treasury, Role::CodeManager]
+        .into_iter()
+        .filter(|role| ROLES.has(deps.storage, (role.as_str(), &address)))
+        .collect();
+
+    Ok(RolesResponse {
+        is_admin: config.admin.as_ref() == Some(&address),
+        address,
+        roles,
+    })
+}
+
+pub fn query_role_members(
+    deps: Deps,
+    role: Role,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RoleMembersResponse> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let members = ROLES
+        .prefix(role.as_str())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(page_limit(limit))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(RoleMembersResponse { role, members })
+}
+
+pub fn query_artist_list(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ArtistListResponse> {
+    let start_after = start_after
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let artists = ARTIST_LIST
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(page_limit(limit))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ArtistListResponse { artists })
+}
+
 pub fn query_collection(deps: Deps, artist: String) -> StdResult<CollectionResponse> {
     let artist_addr = deps.api.addr_validate(&artist)?;
-    let collections = ARTIST_COLLECTIONS.may_load(deps.storage, &artist_addr)?.unwrap_or_default();
-    
-    let collection = collections.first()
-        .and_then(|symbol| COLLECTIONS.may_load(deps.storage, symbol.clone()).ok().flatten());
-    
+    let collection = collections()
+        .idx
+        .artist
+        .prefix(artist_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .next()
+        .transpose()?
+        .map(|(_, collection)| collection);
+
+    Ok(CollectionResponse { collection })
+}
+
+pub fn query_collection_by_symbol(deps: Deps, symbol: String) -> StdResult<CollectionDetailResponse> {
+    let collection = collections()
+        .may_load(deps.storage, symbol)?
+        .map(|collection| collection_details(deps, collection))
+        .transpose()?;
+
+    Ok(CollectionDetailResponse { collection })
+}
+
+pub fn query_collections_by_minter(
+    deps: Deps,
+    minter: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CollectionDetailsResponse> {
+    let minter = deps.api.addr_validate(&minter)?;
+    let limit = page_limit(limit);
+    let start = start_after.map(Bound::exclusive);
+
+    let collections = collections()
+        .idx
+        .minter
+        .prefix(minter)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.and_then(|(_, collection)| collection_details(deps, collection)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let next = if collections.len() == limit {
+        collections.last().map(|details| details.collection.symbol.clone())
+    } else {
+        None
+    };
+
+    Ok(CollectionDetailsResponse { collections, next })
+}
+
+pub fn query_collections(deps: Deps, symbols: Vec<String>) -> StdResult<CollectionDetailsResponse> {
+    if symbols.len() > MAX_LIMIT as usize {
+        return Err(StdError::generic_err(format!("Cannot query more than {} collections", MAX_LIMIT)));
+    }
+
+    let collections = symbols
+        .into_iter()
+        .filter_map(|symbol| collections().may_load(deps.storage, symbol).transpose())
+        .map(|item| item.and_then(|collection| collection_details(deps, collection)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(CollectionDetailsResponse { collections, next: None })
+}
+
+/// Attaches live pass counts from the collection's pass_nft contract
+fn collection_details(deps: Deps, collection: Collection) -> StdResult<CollectionDetails> {
+    let query = NftQueryMsg::Extension { msg: PassQuery::GetArtistInfo {} };
+    let info: ArtistInfoResponse = deps
+        .querier
+        .query_wasm_smart(collection.contract_address.to_string(), &query)?;
+    let stats = CollectionStats {
+        total_passes: info.total_passes,
+        active_passes: info.active_passes,
+    };
+
+    Ok(CollectionDetails { collection, stats })
+}
+
+pub fn query_collection_by_address(deps: Deps, contract_address: String) -> StdResult<CollectionResponse> {
+    let contract_address = deps.api.addr_validate(&contract_address)?;
+    let collection = collections()
+        .idx
+        .contract_address
+        .item(deps.storage, contract_address.clone())?
+        .map(|(_, collection)| collection);
+    let collection = match collection {
+        Some(collection) => Some(collection),
+        None => archived_collections()
+            .idx
+            .contract_address
+            .item(deps.storage, contract_address)?
+            .map(|(_, collection)| collection),
+    };
+
     Ok(CollectionResponse { collection })
 }
 
 pub fn query_artist_collections(
     deps: Deps,
     artist: String,
+    start_after: Option<String>,
     limit: Option<u32>,
+    order: Option<SortOrder>,
+    filter: Option<CollectionFilter>,
 ) -> StdResult<CollectionsResponse> {
     let artist_addr = deps.api.addr_validate(&artist)?;
-    let collections = ARTIST_COLLECTIONS.may_load(deps.storage, &artist_addr)?.unwrap_or_default();
-    
-    let limit = limit.unwrap_or(10) as usize;
-    let collections = collections
-        .into_iter()
-        .filter_map(|symbol| COLLECTIONS.may_load(deps.storage, symbol).ok().flatten())
-        .take(limit)
-        .collect();
-    
-    Ok(CollectionsResponse { collections })
+    let (min, max) = symbol_bounds(start_after, order);
+    let entries = collections()
+        .idx
+        .artist
+        .prefix(artist_addr)
+        .range(deps.storage, min, max, order.unwrap_or_default().into())
+        .map(|item| item.map(|(_, collection)| collection));
+
+    page_collections(deps, Box::new(entries), filter, false, limit)
 }
 
 pub fn query_all_collections(
     deps: Deps,
+    start_after: Option<String>,
     limit: Option<u32>,
+    order: Option<SortOrder>,
+    filter: Option<CollectionFilter>,
+    include_inactive: bool,
 ) -> StdResult<CollectionsResponse> {
-    let limit = limit.unwrap_or(10) as usize;
-    let collections: Vec<Collection> = COLLECTIONS
-        .range(deps.storage, None, None, cosmwasm_std::Order::Ascending)
-        .take(limit)
-        .filter_map(|item| item.ok().map(|(_, collection)| collection))
-        .collect();
+    let (min, max) = symbol_bounds(start_after, order);
+    let order = order.unwrap_or_default().into();
+
+    // Walk the artist or minter index when filtering on one of them
+    let by_artist = filter.as_ref().and_then(|f| f.artist.as_ref());
+    let by_minter = filter.as_ref().and_then(|f| f.minter.as_ref());
+    let entries: Box<dyn Iterator<Item = StdResult<(String, Collection)>>> = match (by_artist, by_minter) {
+        (Some(artist), _) => Box::new(
+            collections()
+                .idx
+                .artist
+                .prefix(deps.api.addr_validate(artist)?)
+                .range(deps.storage, min, max, order),
+        ),
+        (None, Some(minter)) => Box::new(
+            collections()
+                .idx
+                .minter
+                .prefix(deps.api.addr_validate(minter)?)
+                .range(deps.storage, min, max, order),
+        ),
+        (None, None) => Box::new(collections().range(deps.storage, min, max, order)),
+    };
+
+    // Without a status filter, hide inactive collections unless asked
+    let active_only = !include_inactive && !matches!(
+        filter,
+        Some(CollectionFilter { status: Some(_), .. })
+    );
+    let entries = entries.map(|item| item.map(|(_, collection)| collection));
+    page_collections(deps, Box::new(entries), filter, active_only, limit)
+}
+
+/// Range bounds over symbols for a page starting after `start_after`
+fn symbol_bounds(
+    start_after: Option<String>,
+    order: Option<SortOrder>,
+) -> (Option<Bound<'static, String>>, Option<Bound<'static, String>>) {
+    let start = start_after.map(Bound::exclusive);
+    match order.unwrap_or_default() {
+        SortOrder::Ascending => (start, None),
+        SortOrder::Descending => (None, start),
+    }
+}
+
+/// Applies `filter` and takes one page. At most `MAX_SCAN` entries are read,
+/// so a sparse filter can return a short or empty page. `next` points at the
+/// last symbol read unless the entries ran out.
+fn page_collections(
+    deps: Deps,
+    entries: Box<dyn Iterator<Item = StdResult<Collection>> + '_>,
+    filter: Option<CollectionFilter>,
+    active_only: bool,
+    limit: Option<u32>,
+) -> StdResult<CollectionsResponse> {
+    let filter = filter.unwrap_or_default();
+    let artist = filter.artist.map(|a| deps.api.addr_validate(&a)).transpose()?;
+    let minter = filter.minter.map(|m| deps.api.addr_validate(&m)).transpose()?;
+    let limit = page_limit(limit);
+
+    let matches = |collection: &Collection| {
+        !matches!(&artist, Some(a) if collection.artist != *a)
+            && !matches!(&minter, Some(m) if collection.minter != *m)
+            && !matches!(filter.created_after, Some(t) if collection.created_at < t)
+            && !matches!(filter.created_before, Some(t) if collection.created_at >= t)
+            && !matches!(filter.status, Some(s) if collection.status != s)
+            && (!active_only || collection.status == CollectionStatus::Active)
+    };
+
+    let mut collections = vec![];
+    let mut next = None;
+    for (scanned, item) in entries.enumerate() {
+        let collection = item?;
+        let symbol = collection.symbol.clone();
+        if matches(&collection) {
+            collections.push(collection);
+        }
+        if collections.len() == limit || scanned + 1 == MAX_SCAN as usize {
+            next = Some(symbol);
+            break;
+        }
+    }
+
+    Ok(CollectionsResponse { collections, next })
+}
+
 
-    Ok(CollectionsResponse { collections })
+pub fn query_is_symbol_available(deps: Deps, env: Env, symbol: String) -> StdResult<SymbolAvailabilityResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let valid = config.validation.check_symbol(&symbol).is_ok();
+    let taken = SYMBOL_TAKEN.may_load(deps.storage, symbol.clone())?.unwrap_or(false);
+    let reservation = active_reservation(deps.storage, &env.block, &symbol)?;
+
+    Ok(SymbolAvailabilityResponse {
+        available: valid && !taken && reservation.is_none(),
+        valid,
+        taken,
+        reserved_by: reservation.as_ref().map(|r| r.artist.clone()),
+        reserved_until: reservation.map(|r| r.expires),
+        symbol,
+    })
+}
+
+pub fn query_predict_collection_address(
+    deps: Deps,
+    env: Env,
+    symbol: String,
+) -> StdResult<PredictedAddressResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let contract_address = predict_collection_address(deps, &env, config.nft_code_id, &symbol)?;
+    Ok(PredictedAddressResponse { symbol, contract_address })
+}
+
+pub fn query_collection_code_ids(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<CollectionCodeIdsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let start = start_after.map(Bound::exclusive);
+
+    let entries = collections()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(page_limit(limit))
+        .map(|item| {
+            let (symbol, collection) = item?;
+            let code_id = deps
+                .querier
+                .query_wasm_contract_info(collection.contract_address.to_string())?
+                .code_id;
+            Ok(CollectionCodeIdInfo {
+                last_migration: MIGRATION_STATUS.may_load(deps.storage, symbol.clone())?,
+                symbol,
+                contract_address: collection.contract_address,
+                code_id,
+                up_to_date: code_id == config.nft_code_id,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(CollectionCodeIdsResponse {
+        nft_code_id: config.nft_code_id,
+        collections: entries,
+    })
 }
 
+pub fn query_archived_collections(
+    deps: Deps,
+    start_after: Option<(String, u32)>,
+    limit: Option<u32>,
+) -> StdResult<ArchivedCollectionsResponse> {
+    let limit = page_limit(limit);
+    let start = start_after.map(Bound::exclusive);
+
+    let collections = archived_collections()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let ((_, generation), collection) = item?;
+            let code_id = deps
+                .querier
+                .query_wasm_contract_info(collection.contract_address.to_string())?
+                .code_id;
+            Ok(ArchivedCollection { generation, code_id, collection })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
 
-pub fn query_is_symbol_available(deps: Deps, symbol: String) -> StdResult<bool> {
-    let is_taken = SYMBOL_TAKEN.may_load(deps.storage, symbol)?.unwrap_or(false);
-    Ok(!is_taken)
-}
\ No newline at end of file
+    let next = if collections.len() == limit {
+        collections
+            .last()
+            .map(|archived| (archived.collection.symbol.clone(), archived.generation))
+    } else {
+        None
+    };
+
+    Ok(ArchivedCollectionsResponse { collections, next })
+}
@@ -1,12 +1,17 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, BlockInfo, Coin, Order, StdResult};
+use semver::Version;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+use cw_utils::Expiration;
+use pass_nft::state::DEFAULT_DENOM;
 use crate::error::ContractError;
+use crate::helpers::validate_pass_terms;
 
 #[cw_serde]
 pub struct Config {
     pub nft_code_id: u64,
-    pub admin: Addr,
+    /// `None` once the admin has been renounced
+    pub admin: Option<Addr>,
     pub price: u128,
     pub duration: u64,
     pub grace_period: u64,  
@@ -14,6 +19,310 @@ pub struct Config {
     //royalty 
     pub house_percentage: u32,
     pub artist_percentage: u32,
+    #[serde(default)]
+    pub self_service: SelfServiceConfig,
+    #[serde(default)]
+    pub bounds: CollectionBounds,
+    #[serde(default)]
+    pub validation: ValidationPolicy,
+}
+
+/// Longest symbol a policy may allow. Instantiate2 salts are capped at 64
+/// bytes and released symbols get a `-<generation>` suffix.
+pub const MAX_SYMBOL_LENGTH: u32 = 32;
+
+/// Rules for the symbol, name and metadata of new collections
+#[cw_serde]
+pub struct ValidationPolicy {
+    pub min_symbol_length: u32,
+    pub max_symbol_length: u32,
+    pub symbol_charset: SymbolCharset,
+    /// Symbols no collection can be created with
+    pub reserved_symbols: Vec<String>,
+    pub max_name_length: u32,
+    /// Schemes `collection_info` must be a URI of, e.g. `https` or `ipfs`.
+    /// Any `collection_info` is accepted when empty.
+    pub allowed_uri_schemes: Vec<String>,
+}
+
+impl Default for ValidationPolicy {
+    fn default() -> Self {
+        Self {
+            min_symbol_length: 1,
+            max_symbol_length: MAX_SYMBOL_LENGTH,
+            symbol_charset: SymbolCharset::default(),
+            reserved_symbols: vec![],
+            max_name_length: 128,
+            allowed_uri_schemes: vec![],
+        }
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum SymbolCharset {
+    /// ASCII `A-Z` and `0-9`
+    #[default]
+    UppercaseAlphanumeric,
+    /// ASCII `A-Z` only
+    Uppercase,
+}
+
+impl SymbolCharset {
+    fn allows(&self, c: char) -> bool {
+        match self {
+            SymbolCharset::UppercaseAlphanumeric => c.is_ascii_uppercase() || c.is_ascii_digit(),
+            SymbolCharset::Uppercase => c.is_ascii_uppercase(),
+        }
+    }
+}
+
+impl ValidationPolicy {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.min_symbol_length == 0
+            || self.min_symbol_length > self.max_symbol_length
+            || self.max_symbol_length > MAX_SYMBOL_LENGTH
+            || self.max_name_length == 0
+            || self.allowed_uri_schemes.iter().any(|scheme| scheme.is_empty())
+        {
+            return Err(ContractError::InvalidValidationPolicy {});
+        }
+        Ok(())
+    }
+
+    pub fn check_collection(&self, name: &str, symbol: &str, collection_info: &str) -> Result<(), ContractError> {
+        self.check_symbol(symbol)?;
+
+        let name_length = name.chars().count() as u32;
+        if name.trim().is_empty() || name_length > self.max_name_length {
+            return Err(ContractError::InvalidNameLength { max: self.max_name_length });
+        }
+
+        if !self.allowed_uri_schemes.is_empty() {
+            let valid = collection_info.split_once("://").is_some_and(|(scheme, rest)| {
+                !rest.is_empty() && self.allowed_uri_schemes.iter().any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+            });
+            if !valid {
+                return Err(ContractError::InvalidCollectionInfo {});
+            }
+        }
+        Ok(())
+    }
+
+    pub fn check_symbol(&self, symbol: &str) -> Result<(), ContractError> {
+        let symbol_length = symbol.chars().count() as u32;
+        if symbol_length < self.min_symbol_length || symbol_length > self.max_symbol_length {
+            return Err(ContractError::InvalidSymbolLength {
+                min: self.min_symbol_length,
+                max: self.max_symbol_length,
+            });
+        }
+        if !symbol.chars().all(|c| self.symbol_charset.allows(c)) {
+            return Err(ContractError::InvalidSymbolCharacters {});
+        }
+        if self.reserved_symbols.iter().any(|reserved| reserved == symbol) {
+            return Err(ContractError::ReservedSymbol { symbol: symbol.to_string() });
+        }
+        Ok(())
+    }
+}
+
+/// Limits on the terms a collection may override at creation
+#[cw_serde]
+#[derive(Default)]
+pub struct CollectionBounds {
+    /// Price bounds are in `uxion` and do not apply to other denoms
+    pub min_price: Option<u128>,
+    pub max_price: Option<u128>,
+    pub min_duration: Option<u64>,
+    pub max_duration: Option<u64>,
+    pub max_grace_period: Option<u64>,
+    pub min_house_percentage: Option<u32>,
+    /// Denoms other than `uxion` collections may be priced in
+    pub accepted_denoms: Vec<String>,
+}
+
+impl CollectionBounds {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        let inverted = matches!((self.min_price, self.max_price), (Some(min), Some(max)) if min > max)
+            || matches!((self.min_duration, self.max_duration), (Some(min), Some(max)) if min > max)
+            || self.min_house_percentage.is_some_and(|min| min > 100);
+        if inverted {
+            return Err(ContractError::InvalidBounds {});
+        }
+        Ok(())
+    }
+}
+
+/// Terms a collection can set instead of the factory defaults
+#[cw_serde]
+#[derive(Default)]
+pub struct CollectionOverrides {
+    pub price: Option<u128>,
+    /// A denom other than `uxion` needs `price` set as well
+    pub denom: Option<String>,
+    pub duration: Option<u64>,
+    pub grace_period: Option<u64>,
+    pub royalties: Option<RoyaltySplit>,
+}
+
+#[cw_serde]
+pub struct RoyaltySplit {
+    pub house_percentage: u32,
+    pub artist_percentage: u32,
+}
+
+/// Effective pass terms of a collection, after applying its overrides
+#[cw_serde]
+pub struct CollectionTerms {
+    pub pass_price: u128,
+    pub pass_denom: String,
+    pub pass_duration: u64,
+    pub grace_period: u64,
+    pub house_percentage: u32,
+    pub artist_percentage: u32,
+}
+
+/// Lets artists create their own collections without a factory role
+#[cw_serde]
+#[derive(Default)]
+pub struct SelfServiceConfig {
+    pub enabled: bool,
+    /// Paid by the artist on creation and sent to `payment_address`
+    pub creation_fee: Option<Coin>,
+    pub list_mode: ArtistListMode,
+    /// Caps how many collections an artist can create for themselves
+    pub max_collections_per_artist: Option<u32>,
+}
+
+/// How `ARTIST_LIST` is applied to self-service creations
+#[cw_serde]
+#[derive(Default)]
+pub enum ArtistListMode {
+    #[default]
+    Disabled,
+    Allowlist,
+    Denylist,
+}
+
+impl Config {
+    /// Applies `overrides` on top of the factory defaults. Only overridden
+    /// values are checked against the bounds.
+    pub fn collection_terms(&self, overrides: CollectionOverrides) -> Result<CollectionTerms, ContractError> {
+        let bounds = &self.bounds;
+        let out_of_bounds = |field: &str| ContractError::OverrideOutOfBounds { field: field.to_string() };
+
+        let other_denom = overrides.denom.as_ref().filter(|denom| *denom != DEFAULT_DENOM);
+        if let Some(denom) = other_denom {
+            if !bounds.accepted_denoms.contains(denom) {
+                return Err(ContractError::UnsupportedDenom { denom: denom.clone() });
+            }
+            // The default price is a uxion amount
+            if overrides.price.is_none() {
+                return Err(ContractError::PriceRequiredForDenom { denom: denom.clone() });
+            }
+        }
+        if let Some(price) = overrides.price {
+            let out_of_range =
+                bounds.min_price.is_some_and(|min| price < min) || bounds.max_price.is_some_and(|max| price > max);
+            if other_denom.is_none() && out_of_range {
+                return Err(out_of_bounds("price"));
+            }
+        }
+        if let Some(duration) = overrides.duration {
+            if duration == 0
+                || bounds.min_duration.is_some_and(|min| duration < min)
+                || bounds.max_duration.is_some_and(|max| duration > max)
+            {
+                return Err(out_of_bounds("duration"));
+            }
+        }
+        if let Some(grace_period) = overrides.grace_period {
+            if bounds.max_grace_period.is_some_and(|max| grace_period > max) {
+                return Err(out_of_bounds("grace_period"));
+            }
+        }
+        if let Some(royalties) = &overrides.royalties {
+            if royalties.house_percentage + royalties.artist_percentage != 100 {
+                return Err(ContractError::InvalidRoyalties {});
+            }
+            if bounds.min_house_percentage.is_some_and(|min| royalties.house_percentage < min) {
+                return Err(out_of_bounds("house_percentage"));
+            }
+        }
+
+        let royalties = overrides.royalties.unwrap_or(RoyaltySplit {
+            house_percentage: self.house_percentage,
+            artist_percentage: self.artist_percentage,
+        });
+        let terms = CollectionTerms {
+            pass_price: overrides.price.unwrap_or(self.price),
+            pass_denom: overrides.denom.unwrap_or_else(|| DEFAULT_DENOM.to_string()),
+            pass_duration: overrides.duration.unwrap_or(self.duration),
+            grace_period: overrides.grace_period.unwrap_or(self.grace_period),
+            house_percentage: royalties.house_percentage,
+            artist_percentage: royalties.artist_percentage,
+        };
+        // An override can pair with a default the collection would reject
+        validate_pass_terms(terms.pass_duration, terms.grace_period)?;
+        Ok(terms)
+    }
+
+    pub fn ensure_admin(&self, sender: &Addr) -> Result<(), ContractError> {
+        match &self.admin {
+            Some(admin) if admin == sender => Ok(()),
+            _ => Err(ContractError::Unauthorized {}),
+        }
+    }
+}
+
+/// Operational roles that can be granted next to the admin, who
+/// implicitly holds all of them
+#[cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    /// Onboards artists through `CreateCollection`
+    CollectionCreator,
+    /// Manages royalties and the payment address
+    Treasury,
+    /// Updates the pass_nft code id and migrates collections
+    CodeManager,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::CollectionCreator => "collection_creator",
+            Role::Treasury => "treasury",
+            Role::CodeManager => "code_manager",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Passes when `sender` is the admin or has been granted `role`
+pub fn ensure_role(
+    storage: &dyn cosmwasm_std::Storage,
+    config: &Config,
+    sender: &Addr,
+    role: Role,
+) -> Result<(), ContractError> {
+    if config.admin.as_ref() == Some(sender) || ROLES.has(storage, (role.as_str(), sender)) {
+        return Ok(());
+    }
+    Err(ContractError::MissingRole { role })
+}
+
+/// Admin proposed through `ProposeNewAdmin`, waiting to accept
+#[cw_serde]
+pub struct PendingAdmin {
+    pub new_admin: Addr,
+    pub expires: Option<Expiration>,
 }
 
 #[cw_serde]
@@ -28,10 +337,33 @@ pub struct Collection {
      // collection-specific royalty settings
      pub house_percentage: u32,
      pub artist_percentage: u32,
+    // effective pass terms, the factory defaults unless overridden
+    #[serde(default)]
+    pub pass_price: u128,
+    #[serde(default)]
+    pub pass_denom: String,
+    #[serde(default)]
+    pub pass_duration: u64,
+    #[serde(default)]
+    pub grace_period: u64,
+    #[serde(default)]
+    pub status: CollectionStatus,
+}
+
+/// Lifecycle of a registry entry. Inactive and archived collections have
+/// their pass_nft paused; archiving cannot be undone.
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum CollectionStatus {
+    #[default]
+    Active,
+    Inactive,
+    Archived,
 }
 
 
 impl Collection {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: String,
         symbol: String,
@@ -40,8 +372,7 @@ impl Collection {
         contract_address: Addr,
         created_at: u64,
         collection_info: String,
-        house_percentage: u32,
-        artist_percentage: u32,
+        terms: CollectionTerms,
     ) -> Self {
         Self {
             name,
@@ -51,8 +382,13 @@ impl Collection {
             contract_address,
             created_at,
             collection_info,
-            house_percentage,
-            artist_percentage
+            house_percentage: terms.house_percentage,
+            artist_percentage: terms.artist_percentage,
+            pass_price: terms.pass_price,
+            pass_denom: terms.pass_denom,
+            pass_duration: terms.pass_duration,
+            grace_period: terms.grace_period,
+            status: CollectionStatus::Active,
         }
     }
 
@@ -86,37 +422,216 @@ pub fn save_new_collection(
     COLLECTION_COUNT.update(storage, |count| {
         count
             .checked_add(1)
-            .ok_or_else(|| ContractError::MaxSupplyReached { })
+            .ok_or(ContractError::MaxSupplyReached {})
     })?;
 
-    // Save collection data
-    COLLECTIONS.save(storage, collection.symbol.clone(), collection)?;
+    // Save collection data, the indexes follow
+    collections().save(storage, collection.symbol.clone(), collection)?;
 
     // Mark the symbol as taken
     SYMBOL_TAKEN.save(storage, collection.symbol.clone(), &true)?;
 
-    // Update the artist's collections
-    let mut artist_collections = ARTIST_COLLECTIONS
-        .may_load(storage, &collection.artist)?
-        .unwrap_or_default();
-    if !artist_collections.contains(&collection.symbol) {
-        artist_collections.push(collection.symbol.clone());
-    }
-    ARTIST_COLLECTIONS.save(storage, &collection.artist, &artist_collections)?;
-
     Ok(())
 }
 
+/// Outcome of the last factory-driven migration of a collection.
+#[cw_serde]
+pub struct MigrationStatus {
+    pub code_id: u64,
+    pub success: bool,
+    pub error: Option<String>,
+    pub updated_at: u64,
+}
+
+/// Drops everything `save_new_collection` wrote for `symbol`. Used by the
+/// migration of collections that were never instantiated and when an
+/// archived collection releases its symbol.
+pub fn remove_collection(
+    storage: &mut dyn cosmwasm_std::Storage,
+    symbol: &str,
+) -> Result<Collection, ContractError> {
+    let collection = collections()
+        .may_load(storage, symbol.to_string())?
+        .ok_or(ContractError::CollectionNotFound {})?;
 
+    collections().remove(storage, symbol.to_string())?;
+    SYMBOL_TAKEN.remove(storage, symbol.to_string());
 
+    COLLECTION_COUNT.update(storage, |count| -> Result<u64, ContractError> {
+        Ok(count.saturating_sub(1))
+    })?;
 
+    Ok(collection)
+}
+
+/// Hands out a fresh sub-message reply id, so every instantiation in a
+/// transaction can be correlated with its own reply.
+pub fn next_reply_id(storage: &mut dyn cosmwasm_std::Storage) -> Result<u64, ContractError> {
+    let id = REPLY_ID_COUNTER.may_load(storage)?.unwrap_or_default() + 1;
+    REPLY_ID_COUNTER.save(storage, &id)?;
+    Ok(id)
+}
+
+/// Runs the state migrations needed to bring storage written by
+/// `from` up to the current contract version. Each step is keyed by the
+/// version that introduced it.
+pub fn migrate_state(
+    storage: &mut dyn cosmwasm_std::Storage,
+    from: &Version,
+) -> Result<Vec<String>, ContractError> {
+    let mut removed = vec![];
+
+    // 0.2.0: replies are correlated by reply id. Collections left without a
+    // contract address by the old "first empty contract_address" scan are
+    // orphans that only block their symbol, so drop them.
+    if *from < Version::new(0, 2, 0) {
+        let orphans = collections()
+            .range(storage, None, None, Order::Ascending)
+            .filter_map(|item| match item {
+                Ok((symbol, collection)) if collection.contract_address.as_str().is_empty() => Some(Ok(symbol)),
+                Ok(_) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for symbol in orphans {
+            remove_collection(storage, &symbol)?;
+            removed.push(symbol);
+        }
+
+        // Collections now record their pass terms. Price, duration and grace
+        // period could not be changed on the factory before, so every existing
+        // collection was created with the current defaults. Saving through the
+        // indexed map also builds the artist, minter and address indexes.
+        let config = CONFIG.load(storage)?;
+        let existing = collections()
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (symbol, mut collection) in existing {
+            collection.pass_price = config.price;
+            collection.pass_denom = DEFAULT_DENOM.to_string();
+            collection.pass_duration = config.duration;
+            collection.grace_period = config.grace_period;
+            collections().save(storage, symbol, &collection)?;
+        }
+
+        // The per-artist symbol lists are replaced by the artist index
+        const LEGACY_ARTIST_COLLECTIONS: Map<&Addr, Vec<String>> = Map::new("artist_collections");
+        let artists = LEGACY_ARTIST_COLLECTIONS
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for artist in artists {
+            LEGACY_ARTIST_COLLECTIONS.remove(storage, &artist);
+        }
+    }
+
+    Ok(removed)
+}
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
-pub const COLLECTIONS: Map<String, Collection> = Map::new("collections");
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+
+/// Allow or deny list of artists, depending on `SelfServiceConfig.list_mode`
+pub const ARTIST_LIST: Map<&Addr, bool> = Map::new("artist_list");
+
+/// Role grants keyed by (role, address)
+pub const ROLES: Map<(&str, &Addr), bool> = Map::new("roles");
+
+pub struct CollectionIndexes<'a> {
+    pub artist: MultiIndex<'a, Addr, Collection, String>,
+    pub minter: MultiIndex<'a, Addr, Collection, String>,
+    pub contract_address: UniqueIndex<'a, Addr, Collection, String>,
+}
+
+impl<'a> IndexList<Collection> for CollectionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Collection>> + '_> {
+        let v: Vec<&dyn Index<Collection>> = vec![&self.artist, &self.minter, &self.contract_address];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Collections keyed by symbol, indexed by artist, minter and contract address
+pub fn collections<'a>() -> IndexedMap<'a, String, Collection, CollectionIndexes<'a>> {
+    let indexes = CollectionIndexes {
+        artist: MultiIndex::new(
+            |collection: &Collection| collection.artist.clone(),
+            "collections",
+            "collections__artist",
+        ),
+        minter: MultiIndex::new(
+            |collection: &Collection| collection.minter.clone(),
+            "collections",
+            "collections__minter",
+        ),
+        contract_address: UniqueIndex::new(
+            |collection: &Collection| collection.contract_address.clone(),
+            "collections__address",
+        ),
+    };
+    IndexedMap::new("collections", indexes)
+}
 
 pub const SYMBOL_TAKEN: Map<String, bool> = Map::new("symbol_taken");
 
-pub const ARTIST_COLLECTIONS: Map<&Addr, Vec<String>> = Map::new("artist_collections");
+/// Symbol held for an artist ahead of their launch
+#[cw_serde]
+pub struct SymbolReservation {
+    pub artist: Addr,
+    pub expires: Expiration,
+}
+
+/// Reservations for symbols that have no collection yet
+pub const SYMBOL_RESERVATIONS: Map<String, SymbolReservation> = Map::new("symbol_reservations");
+
+/// The reservation on `symbol`, unless there is none or it has expired
+pub fn active_reservation(
+    storage: &dyn cosmwasm_std::Storage,
+    block: &BlockInfo,
+    symbol: &str,
+) -> StdResult<Option<SymbolReservation>> {
+    Ok(SYMBOL_RESERVATIONS
+        .may_load(storage, symbol.to_string())?
+        .filter(|reservation| !reservation.expires.is_expired(block)))
+}
+
+pub struct ArchivedCollectionIndexes<'a> {
+    pub contract_address: UniqueIndex<'a, Addr, Collection, (String, u32)>,
+}
+
+impl<'a> IndexList<Collection> for ArchivedCollectionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Collection>> + '_> {
+        let v: Vec<&dyn Index<Collection>> = vec![&self.contract_address];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Archived collections whose symbol was released, keyed by (symbol,
+/// generation) and indexed by contract address
+pub fn archived_collections<'a>() -> IndexedMap<'a, (String, u32), Collection, ArchivedCollectionIndexes<'a>> {
+    let indexes = ArchivedCollectionIndexes {
+        contract_address: UniqueIndex::new(
+            |collection: &Collection| collection.contract_address.clone(),
+            "archived_collections__address",
+        ),
+    };
+    IndexedMap::new("archived_collections", indexes)
+}
+
+/// How many times a symbol was released. Part of the Instantiate2 salt, so a
+/// reused symbol gets a new address.
+pub const SYMBOL_GENERATION: Map<String, u32> = Map::new("symbol_generation");
+
+pub const COLLECTION_COUNT: Item<u64> = Item::new("collection_count");
+
+/// Symbols of collections whose pass_nft instantiation is still in flight,
+/// keyed by the reply id of the instantiate sub-message.
+pub const PENDING_INSTANTIATIONS: Map<u64, String> = Map::new("pending_instantiations");
+
+pub const REPLY_ID_COUNTER: Item<u64> = Item::new("reply_id_counter");
+
+/// Symbols of collections with a `WasmMsg::Migrate` in flight, keyed by the
+/// reply id of the migrate sub-message.
+pub const PENDING_MIGRATIONS: Map<u64, String> = Map::new("pending_migrations");
 
-pub const COLLECTION_COUNT: Item<u64> = Item::new("collection_count");
\ No newline at end of file
+pub const MIGRATION_STATUS: Map<String, MigrationStatus> = Map::new("migration_status");
@@ -1,13 +1,29 @@
 #[cfg(test)]
 mod tests {
+    use std::marker::PhantomData;
     use cosmwasm_std::{
-        from_json, testing::{mock_dependencies, mock_env, mock_info},
-        Addr, Response, DepsMut,
+        from_json, testing::{mock_env, mock_info, MockQuerier, MockStorage},
+        coin, coins, to_json_binary, Addr, BankMsg, CodeInfoResponse, ContractResult, Env, HexBinary, OwnedDeps,
+        Response, DepsMut, StdError, SystemError, SystemResult, WasmQuery,
     };
+    use cw_multi_test::MockApiBech32;
+    use cw_utils::Expiration;
+    use cw_storage_plus::Map;
     use crate::{
-        msg::{InstantiateMsg, ExecuteMsg, QueryMsg, ConfigResponse, CollectionResponse},
+        msg::{
+            InstantiateMsg, ExecuteMsg, QueryMsg, ConfigResponse, CollectionResponse,
+            PredictedAddressResponse, PendingAdminResponse, RolesResponse, RoleMembersResponse,
+            ArtistListResponse, SyncField, SyncTarget, CollectionsResponse, CollectionFilter,
+            SortOrder, CollectionDetailResponse, CollectionDetailsResponse, SymbolAvailabilityResponse,
+        },
         error::ContractError,
-        contract::{instantiate, query, execute},
+        contract::{instantiate, query, execute, migrate},
+        msg::MigrateMsg,
+        state::{
+            remove_collection, save_new_collection, ArtistListMode, Collection, CollectionBounds, CollectionOverrides,
+            CollectionStatus, CollectionTerms, Role, RoyaltySplit, SelfServiceConfig, SymbolCharset,
+            ValidationPolicy, collections,
+        },
     };
 
     // Constants for testing
@@ -21,6 +37,40 @@ mod tests {
     const HOUSE_ROYALTY: u32 = 30;
     const ARTIST_ROYALTY : u32 = 70;    
 
+    fn addr(name: &str) -> Addr {
+        MockApiBech32::new("xion").addr_make(name)
+    }
+
+    // Bech32 api and a code info querier, needed to predict Instantiate2 addresses
+    fn mock_deps() -> OwnedDeps<MockStorage, MockApiBech32, MockQuerier> {
+        let mut querier = MockQuerier::default();
+        querier.update_wasm(|query| match query {
+            WasmQuery::CodeInfo { code_id } => {
+                let mut res = CodeInfoResponse::default();
+                res.code_id = *code_id;
+                res.creator = addr(OWNER).to_string();
+                res.checksum = HexBinary::from(vec![*code_id as u8; 32]);
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            WasmQuery::Smart { contract_addr, .. } => {
+                SystemResult::Err(SystemError::NoSuchContract { addr: contract_addr.clone() })
+            }
+            _ => unimplemented!(),
+        });
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApiBech32::new("xion"),
+            querier,
+            custom_query_type: PhantomData,
+        }
+    }
+
+    fn factory_env() -> Env {
+        let mut env = mock_env();
+        env.contract.address = addr("factory");
+        env
+    }
+
     // Helper function to instantiate the contract
     fn setup_contract(deps: DepsMut) -> Response {
         let msg = InstantiateMsg {
@@ -28,13 +78,16 @@ mod tests {
             price: PASS_PRICE,
             duration: PASS_DURATION,
             grace_period: GRACE_PERIOD,
-            payment_address: Addr::unchecked(OWNER),
+            payment_address: addr("payment"),
             house_percentage: HOUSE_ROYALTY,
-            artist_percentage: ARTIST_ROYALTY
+            artist_percentage: ARTIST_ROYALTY,
+            self_service: None,
+            bounds: None,
+            validation: None,
         };
         
         let info = mock_info(OWNER, &[]);
-        let env = mock_env();
+        let env = factory_env();
 
         instantiate(deps, env, info, msg).unwrap()
     }
@@ -47,6 +100,7 @@ mod tests {
             artist,
             minter,
             collection_info: "Test Collection Metadata".to_string(), // Now using proper String
+            overrides: None,
         }
     }
 
@@ -55,27 +109,31 @@ mod tests {
 
         #[test]
         fn proper_initialization() {
-            let mut deps = mock_dependencies();
+            let mut deps = mock_deps();
             let response = setup_contract(deps.as_mut());
             assert_eq!(0, response.messages.len());
 
             // Query and verify config
             let config: ConfigResponse = from_json(
-                &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()
+                query(deps.as_ref(), factory_env(), QueryMsg::Config {}).unwrap()
             ).unwrap();
 
             println!("Config Response: {:?}", config);
             assert_eq!(config.nft_code_id, NFT_CODE_ID);
-            assert_eq!(config.admin, OWNER);
+            assert_eq!(config.admin, Some(OWNER.to_string()));
             assert_eq!(config.total_collections, 0);
+            assert_eq!(config.price, PASS_PRICE);
+            assert_eq!(config.duration, PASS_DURATION);
+            assert_eq!(config.grace_period, GRACE_PERIOD);
+            assert_eq!(config.payment_address, addr("payment"));
         }
 
         #[test]
         fn test_create_collection() {
-            let mut deps = mock_dependencies();
+            let mut deps = mock_deps();
             setup_contract(deps.as_mut());
             
-            let artist = Addr::unchecked(ARTIST);
+            let artist = addr(ARTIST);
             let minter = Addr::unchecked(MINTER);
 
             let msg = create_collection_msg(
@@ -87,7 +145,7 @@ mod tests {
 
             // Execute as admin (not artist)
             let info = mock_info(OWNER, &[]);
-            let response = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            let response = execute(deps.as_mut(), factory_env(), info, msg).unwrap();
             
             println!("Create Collection Response: {:?}", response);
             
@@ -96,10 +154,10 @@ mod tests {
             
             // Query and verify collection
             let collection: CollectionResponse = from_json(
-                &query(
+                query(
                     deps.as_ref(),
-                    mock_env(),
-                    QueryMsg::Collection { artist: ARTIST.to_string() }
+                    factory_env(),
+                    QueryMsg::Collection { artist: addr(ARTIST).to_string() }
                 ).unwrap()
             ).unwrap();
 
@@ -111,29 +169,39 @@ mod tests {
             assert_eq!(collection.artist, artist);
             assert_eq!(collection.minter, minter);
             assert_eq!(collection.collection_info, "Test Collection Metadata");
+
+            // The registry already holds the Instantiate2 address
+            let predicted: PredictedAddressResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::PredictCollectionAddress { symbol: "DRAKE".to_string() }
+                ).unwrap()
+            ).unwrap();
+            assert_eq!(collection.contract_address, predicted.contract_address);
         }
 
         #[test]
         fn test_unauthorized_collection_creation() {
-            let mut deps = mock_dependencies();
+            let mut deps = mock_deps();
             setup_contract(deps.as_mut());
 
             let msg = create_collection_msg(
                 "Test Collection".to_string(),
                 "TEST".to_string(),
-                Addr::unchecked(ARTIST),
+                addr(ARTIST),
                 Addr::unchecked(MINTER),
             );
 
             // Try to create collection as artist (should fail)
             let info = mock_info(ARTIST, &[]);
-            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-            assert_eq!(err, ContractError::Unauthorized {});
+            let err = execute(deps.as_mut(), factory_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::CollectionCreator });
         }
 
         #[test]
         fn test_update_nft_code_id() {
-            let mut deps = mock_dependencies();
+            let mut deps = mock_deps();
             setup_contract(deps.as_mut());
 
             let new_code_id = 456u64;
@@ -143,19 +211,937 @@ mod tests {
 
             // Non-owner attempt should fail
             let info = mock_info(ARTIST, &[]);
-            let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
-            assert_eq!(err, ContractError::Unauthorized {});
+            let err = execute(deps.as_mut(), factory_env(), info, msg.clone()).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::CodeManager });
 
             // Owner attempt should succeed
             let info = mock_info(OWNER, &[]);
-            let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            let res = execute(deps.as_mut(), factory_env(), info, msg).unwrap();
             println!("Update Code ID Response: {:?}", res);
 
             // Verify code ID was updated
             let config: ConfigResponse = from_json(
-                &query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()
+                query(deps.as_ref(), factory_env(), QueryMsg::Config {}).unwrap()
             ).unwrap();
             assert_eq!(config.nft_code_id, new_code_id);
         }
+
+        #[test]
+        fn test_migrate_version_checks() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+
+            // Different contract stored under cw2
+            cw2::set_contract_version(deps.as_mut().storage, "crates.io:other", "0.1.0").unwrap();
+            let err = migrate(deps.as_mut(), factory_env(), MigrateMsg {}).unwrap_err();
+            assert!(matches!(err, ContractError::InvalidMigrationContract { .. }));
+
+            // Newer version stored than the running binary
+            cw2::set_contract_version(deps.as_mut().storage, "crates.io:loop-factory", "99.0.0").unwrap();
+            let err = migrate(deps.as_mut(), factory_env(), MigrateMsg {}).unwrap_err();
+            assert!(matches!(err, ContractError::MigrationDowngrade { .. }));
+
+            // Same version is a no-op upgrade
+            cw2::set_contract_version(deps.as_mut().storage, "crates.io:loop-factory", env!("CARGO_PKG_VERSION")).unwrap();
+            migrate(deps.as_mut(), factory_env(), MigrateMsg {}).unwrap();
+        }
+
+        #[test]
+        fn test_migrate_removes_orphaned_collections() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            cw2::set_contract_version(deps.as_mut().storage, "crates.io:loop-factory", "0.1.0").unwrap();
+
+            // 0.1.0 collections did not record their pass terms
+            let legacy_terms = CollectionTerms {
+                pass_price: 0,
+                pass_denom: String::new(),
+                pass_duration: 0,
+                grace_period: 0,
+                house_percentage: HOUSE_ROYALTY,
+                artist_percentage: ARTIST_ROYALTY,
+            };
+
+            // A 0.1.0 collection whose reply never filled in the address
+            let orphan = Collection::new(
+                "Drake Collection".to_string(),
+                "DRAKE".to_string(),
+                addr(ARTIST),
+                Addr::unchecked(MINTER),
+                Addr::unchecked(""),
+                0,
+                "Test Collection Metadata".to_string(),
+                legacy_terms.clone(),
+            );
+            save_new_collection(deps.as_mut().storage, &orphan).unwrap();
+            let live = Collection::new(
+                "Live Collection".to_string(),
+                "LIVE".to_string(),
+                addr(ARTIST),
+                Addr::unchecked(MINTER),
+                addr("live"),
+                0,
+                "Test Collection Metadata".to_string(),
+                legacy_terms,
+            );
+            // 0.1.0 stored collections in a plain map with a per-artist symbol list
+            let legacy_collections: Map<String, Collection> = Map::new("collections");
+            legacy_collections.save(deps.as_mut().storage, "LIVE".to_string(), &live).unwrap();
+            let legacy_artist_collections: Map<&Addr, Vec<String>> = Map::new("artist_collections");
+            legacy_artist_collections
+                .save(deps.as_mut().storage, &addr(ARTIST), &vec!["DRAKE".to_string(), "LIVE".to_string()])
+                .unwrap();
+
+            let res = migrate(deps.as_mut(), factory_env(), MigrateMsg {}).unwrap();
+            assert!(res.attributes.iter().any(|attr| attr.key == "removed_collections" && attr.value == "DRAKE"));
+
+            // Remaining collections get the terms they were created with
+            let live = collections().load(deps.as_ref().storage, "LIVE".to_string()).unwrap();
+            assert_eq!(live.pass_price, PASS_PRICE);
+            assert_eq!(live.pass_denom, "uxion");
+            assert_eq!(live.pass_duration, PASS_DURATION);
+            assert_eq!(live.grace_period, GRACE_PERIOD);
+
+            // and are reachable through the new indexes
+            let res: CollectionResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::CollectionByAddress { contract_address: addr("live").to_string() },
+                ).unwrap()
+            ).unwrap();
+            assert_eq!(res.collection.unwrap().symbol, "LIVE");
+            let res: CollectionResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::Collection { artist: addr(ARTIST).to_string() }).unwrap()
+            ).unwrap();
+            assert_eq!(res.collection.unwrap().symbol, "LIVE");
+            assert!(!legacy_artist_collections.has(deps.as_ref().storage, &addr(ARTIST)));
+
+            let availability: SymbolAvailabilityResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::IsSymbolAvailable { symbol: "DRAKE".to_string() }).unwrap()
+            ).unwrap();
+            assert!(availability.available);
+
+            let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+            assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        }
+
+        #[test]
+        fn test_admin_transfer() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let new_admin = addr("new_admin");
+
+            let propose = ExecuteMsg::ProposeNewAdmin { new_admin: new_admin.to_string(), expires: None };
+            let err = execute(deps.as_mut(), factory_env(), mock_info(ARTIST, &[]), propose.clone()).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), propose).unwrap();
+
+            let pending: PendingAdminResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::PendingAdmin {}).unwrap()
+            ).unwrap();
+            assert_eq!(pending.pending_admin, Some(new_admin.clone()));
+
+            // Only the proposed address can accept
+            let err = execute(deps.as_mut(), factory_env(), mock_info(ARTIST, &[]), ExecuteMsg::AcceptAdmin {}).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), factory_env(), mock_info(new_admin.as_str(), &[]), ExecuteMsg::AcceptAdmin {}).unwrap();
+
+            let config: ConfigResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::Config {}).unwrap()
+            ).unwrap();
+            assert_eq!(config.admin, Some(new_admin.to_string()));
+
+            let pending: PendingAdminResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::PendingAdmin {}).unwrap()
+            ).unwrap();
+            assert_eq!(pending.pending_admin, None);
+
+            // The previous admin lost its rights
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateNftCodeId { code_id: 456 },
+            ).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::CodeManager });
+        }
+
+        #[test]
+        fn test_admin_proposal_expiry_and_cancel() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let new_admin = addr("new_admin");
+
+            let env = factory_env();
+            let expires = Expiration::AtHeight(env.block.height + 10);
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::ProposeNewAdmin { new_admin: new_admin.to_string(), expires: Some(expires) },
+            ).unwrap();
+
+            let mut late_env = env.clone();
+            late_env.block.height += 10;
+            let err = execute(deps.as_mut(), late_env, mock_info(new_admin.as_str(), &[]), ExecuteMsg::AcceptAdmin {}).unwrap_err();
+            assert_eq!(err, ContractError::AdminProposalExpired {});
+
+            execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), ExecuteMsg::CancelAdminProposal {}).unwrap();
+            let err = execute(deps.as_mut(), env.clone(), mock_info(new_admin.as_str(), &[]), ExecuteMsg::AcceptAdmin {}).unwrap_err();
+            assert_eq!(err, ContractError::NoPendingAdmin {});
+
+            let err = execute(deps.as_mut(), env, mock_info(OWNER, &[]), ExecuteMsg::CancelAdminProposal {}).unwrap_err();
+            assert_eq!(err, ContractError::NoPendingAdmin {});
+        }
+
+        #[test]
+        fn test_renounce_admin() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+
+            execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::ProposeNewAdmin { new_admin: addr("new_admin").to_string(), expires: None },
+            ).unwrap();
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), ExecuteMsg::RenounceAdmin {}).unwrap();
+
+            let config: ConfigResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::Config {}).unwrap()
+            ).unwrap();
+            assert_eq!(config.admin, None);
+
+            // Renouncing also drops the pending proposal
+            let pending: PendingAdminResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::PendingAdmin {}).unwrap()
+            ).unwrap();
+            assert_eq!(pending.pending_admin, None);
+
+            let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), ExecuteMsg::RenounceAdmin {}).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+        }
+
+        #[test]
+        fn test_roles() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let creator = addr("creator");
+            let treasury = addr("treasury");
+
+            // Only the admin manages roles
+            let grant = ExecuteMsg::GrantRole { role: Role::CollectionCreator, address: creator.to_string() };
+            let err = execute(deps.as_mut(), factory_env(), mock_info(creator.as_str(), &[]), grant.clone()).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), grant).unwrap();
+            execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::GrantRole { role: Role::Treasury, address: treasury.to_string() },
+            ).unwrap();
+
+            // A collection creator can onboard artists but not touch royalties
+            let msg = create_collection_msg(
+                "Drake Collection".to_string(),
+                "DRAKE".to_string(),
+                addr(ARTIST),
+                Addr::unchecked(MINTER),
+            );
+            execute(deps.as_mut(), factory_env(), mock_info(creator.as_str(), &[]), msg).unwrap();
+
+            let royalties = ExecuteMsg::UpdateRoyalties { house_percentage: 20, artist_percentage: 80 };
+            let err = execute(deps.as_mut(), factory_env(), mock_info(creator.as_str(), &[]), royalties.clone()).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::Treasury });
+            execute(deps.as_mut(), factory_env(), mock_info(treasury.as_str(), &[]), royalties).unwrap();
+
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(treasury.as_str(), &[]),
+                ExecuteMsg::UpdateNftCodeId { code_id: 456 },
+            ).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::CodeManager });
+
+            let roles: RolesResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::Roles { address: creator.to_string() }).unwrap()
+            ).unwrap();
+            assert_eq!(roles.roles, vec![Role::CollectionCreator]);
+            assert!(!roles.is_admin);
+
+            let members: RoleMembersResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::RoleMembers { role: Role::Treasury, start_after: None, limit: None },
+                ).unwrap()
+            ).unwrap();
+            assert_eq!(members.members, vec![treasury.clone()]);
+
+            // Revoked creators lose access
+            execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::RevokeRole { role: Role::CollectionCreator, address: creator.to_string() },
+            ).unwrap();
+            let msg = create_collection_msg(
+                "Other Collection".to_string(),
+                "OTHER".to_string(),
+                addr(ARTIST),
+                Addr::unchecked(MINTER),
+            );
+            let err = execute(deps.as_mut(), factory_env(), mock_info(creator.as_str(), &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::CollectionCreator });
+        }
+
+        #[test]
+        fn test_self_service_creation() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let artist = addr(ARTIST);
+            let self_service = SelfServiceConfig {
+                enabled: true,
+                creation_fee: Some(coin(50, "uxion")),
+                list_mode: ArtistListMode::Denylist,
+                max_collections_per_artist: Some(1),
+            };
+
+            // Off by default: artists need the creator role
+            let msg = create_collection_msg(
+                "Drake Collection".to_string(),
+                "DRAKE".to_string(),
+                artist.clone(),
+                Addr::unchecked(MINTER),
+            );
+            let err = execute(deps.as_mut(), factory_env(), mock_info(artist.as_str(), &coins(50, "uxion")), msg.clone()).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::CollectionCreator });
+
+            let update = ExecuteMsg::UpdateSelfService { config: self_service.clone() };
+            let err = execute(deps.as_mut(), factory_env(), mock_info(artist.as_str(), &[]), update.clone()).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateSelfService {
+                    config: SelfServiceConfig { creation_fee: Some(coin(0, "uxion")), ..self_service.clone() },
+                },
+            ).unwrap_err();
+            assert!(matches!(err, ContractError::InvalidCreationFee { .. }));
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), update).unwrap();
+
+            // Artists can only create for themselves
+            let err = execute(deps.as_mut(), factory_env(), mock_info("someone", &coins(50, "uxion")), msg.clone()).unwrap_err();
+            assert_eq!(err, ContractError::NotArtist {});
+
+            // The fee must be paid exactly
+            let err = execute(deps.as_mut(), factory_env(), mock_info(artist.as_str(), &coins(40, "uxion")), msg.clone()).unwrap_err();
+            assert!(matches!(err, ContractError::InvalidCreationFee { .. }));
+            let err = execute(deps.as_mut(), factory_env(), mock_info(artist.as_str(), &[]), msg.clone()).unwrap_err();
+            assert!(matches!(err, ContractError::Payment(_)));
+
+            // Denied artists are turned away
+            execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateArtistList { add: vec![artist.to_string()], remove: vec![] },
+            ).unwrap();
+            let list: ArtistListResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::ArtistList { start_after: None, limit: None }).unwrap()
+            ).unwrap();
+            assert_eq!(list.artists, vec![artist.clone()]);
+            let err = execute(deps.as_mut(), factory_env(), mock_info(artist.as_str(), &coins(50, "uxion")), msg.clone()).unwrap_err();
+            assert_eq!(err, ContractError::ArtistNotAllowed {});
+            execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateArtistList { add: vec![], remove: vec![artist.to_string()] },
+            ).unwrap();
+
+            // The fee is forwarded to the payment address
+            let res = execute(deps.as_mut(), factory_env(), mock_info(artist.as_str(), &coins(50, "uxion")), msg).unwrap();
+            assert_eq!(res.messages.len(), 2);
+            assert_eq!(
+                res.messages[1].msg,
+                BankMsg::Send { to_address: addr("payment").to_string(), amount: coins(50, "uxion") }.into()
+            );
+
+            // One self-service collection per artist
+            let msg = create_collection_msg(
+                "Second Collection".to_string(),
+                "SECOND".to_string(),
+                artist.clone(),
+                Addr::unchecked(MINTER),
+            );
+            let err = execute(deps.as_mut(), factory_env(), mock_info(artist.as_str(), &coins(50, "uxion")), msg.clone()).unwrap_err();
+            assert_eq!(err, ContractError::ArtistCollectionLimitReached { limit: 1 });
+
+            // Role holders are not charged nor capped
+            let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &coins(50, "uxion")), msg.clone()).unwrap_err();
+            assert!(matches!(err, ContractError::Payment(_)));
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), msg).unwrap();
+        }
+
+        #[test]
+        fn test_collection_overrides() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let bounds = CollectionBounds {
+                min_price: Some(5),
+                max_price: Some(100),
+                min_duration: Some(86400),
+                max_duration: Some(31536000),
+                max_grace_period: Some(604800),
+                min_house_percentage: Some(10),
+                accepted_denoms: vec!["uusdc".to_string()],
+            };
+
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateCollectionBounds {
+                    bounds: CollectionBounds { min_price: Some(200), ..bounds.clone() },
+                },
+            ).unwrap_err();
+            assert_eq!(err, ContractError::InvalidBounds {});
+            execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateCollectionBounds { bounds: bounds.clone() },
+            ).unwrap();
+
+            let create = |symbol: &str, overrides: CollectionOverrides| ExecuteMsg::CreateCollection {
+                name: format!("{} Collection", symbol),
+                symbol: symbol.to_string(),
+                artist: addr(ARTIST),
+                minter: Addr::unchecked(MINTER),
+                collection_info: "Test Collection Metadata".to_string(),
+                overrides: Some(overrides),
+            };
+
+            let cases = [
+                (CollectionOverrides { price: Some(500), ..Default::default() }, ContractError::OverrideOutOfBounds { field: "price".to_string() }),
+                (CollectionOverrides { duration: Some(0), ..Default::default() }, ContractError::OverrideOutOfBounds { field: "duration".to_string() }),
+                (CollectionOverrides { grace_period: Some(604801), ..Default::default() }, ContractError::OverrideOutOfBounds { field: "grace_period".to_string() }),
+                (
+                    CollectionOverrides { duration: Some(86400), grace_period: Some(172800), ..Default::default() },
+                    ContractError::InvalidGracePeriod {},
+                ),
+                (CollectionOverrides { denom: Some("uatom".to_string()), ..Default::default() }, ContractError::UnsupportedDenom { denom: "uatom".to_string() }),
+                (
+                    CollectionOverrides { denom: Some("uusdc".to_string()), ..Default::default() },
+                    ContractError::PriceRequiredForDenom { denom: "uusdc".to_string() },
+                ),
+                (
+                    CollectionOverrides { royalties: Some(RoyaltySplit { house_percentage: 5, artist_percentage: 95 }), ..Default::default() },
+                    ContractError::OverrideOutOfBounds { field: "house_percentage".to_string() },
+                ),
+                (
+                    CollectionOverrides { royalties: Some(RoyaltySplit { house_percentage: 20, artist_percentage: 20 }), ..Default::default() },
+                    ContractError::InvalidRoyalties {},
+                ),
+            ];
+            for (overrides, expected) in cases {
+                let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), create("BAD", overrides)).unwrap_err();
+                assert_eq!(err, expected);
+            }
+
+            // The uxion price bounds leave prices in other denoms alone
+            let overrides = CollectionOverrides {
+                price: Some(5_000),
+                denom: Some("uusdc".to_string()),
+                duration: None,
+                grace_period: Some(0),
+                royalties: Some(RoyaltySplit { house_percentage: 15, artist_percentage: 85 }),
+            };
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), create("DRAKE", overrides)).unwrap();
+
+            let collection = collections().load(deps.as_ref().storage, "DRAKE".to_string()).unwrap();
+            assert_eq!(collection.pass_price, 5_000);
+            assert_eq!(collection.pass_denom, "uusdc");
+            assert_eq!(collection.pass_duration, PASS_DURATION);
+            assert_eq!(collection.grace_period, 0);
+            assert_eq!(collection.house_percentage, 15);
+            assert_eq!(collection.artist_percentage, 85);
+
+            let config: ConfigResponse = from_json(query(deps.as_ref(), factory_env(), QueryMsg::Config {}).unwrap()).unwrap();
+            assert_eq!(config.bounds, bounds);
+        }
+
+        #[test]
+        fn test_validation_policy() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let create = |name: &str, symbol: &str, collection_info: &str| ExecuteMsg::CreateCollection {
+                name: name.to_string(),
+                symbol: symbol.to_string(),
+                artist: addr(ARTIST),
+                minter: Addr::unchecked(MINTER),
+                collection_info: collection_info.to_string(),
+                overrides: None,
+            };
+
+            // The default policy rejects empty symbols and accepts digits
+            let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), create("Drake", "", "info")).unwrap_err();
+            assert_eq!(err, ContractError::InvalidSymbolLength { min: 1, max: 32 });
+            let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), create("Drake", "DRÄKE", "info")).unwrap_err();
+            assert_eq!(err, ContractError::InvalidSymbolCharacters {});
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), create("Drake", "DRAKE2", "info")).unwrap();
+
+            let policy = ValidationPolicy {
+                min_symbol_length: 3,
+                max_symbol_length: 6,
+                symbol_charset: SymbolCharset::Uppercase,
+                reserved_symbols: vec!["XION".to_string()],
+                max_name_length: 10,
+                allowed_uri_schemes: vec!["https".to_string(), "ipfs".to_string()],
+            };
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateValidationPolicy { policy: ValidationPolicy { max_symbol_length: 33, ..policy.clone() } },
+            ).unwrap_err();
+            assert_eq!(err, ContractError::InvalidValidationPolicy {});
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(ARTIST, &[]),
+                ExecuteMsg::UpdateValidationPolicy { policy: policy.clone() },
+            ).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateValidationPolicy { policy: policy.clone() },
+            ).unwrap();
+
+            let cases = [
+                (create("Drake", "DR", "ipfs://meta"), ContractError::InvalidSymbolLength { min: 3, max: 6 }),
+                (create("Drake", "DRAKES1", "ipfs://meta"), ContractError::InvalidSymbolLength { min: 3, max: 6 }),
+                (create("Drake", "DRAKE3", "ipfs://meta"), ContractError::InvalidSymbolCharacters {}),
+                (create("Xion", "XION", "ipfs://meta"), ContractError::ReservedSymbol { symbol: "XION".to_string() }),
+                (create("Drake Collection", "DRAKE", "ipfs://meta"), ContractError::InvalidNameLength { max: 10 }),
+                (create(" ", "DRAKE", "ipfs://meta"), ContractError::InvalidNameLength { max: 10 }),
+                (create("Drake", "DRAKE", "Test Collection Metadata"), ContractError::InvalidCollectionInfo {}),
+                (create("Drake", "DRAKE", "http://meta"), ContractError::InvalidCollectionInfo {}),
+                (create("Drake", "DRAKE", "ipfs://"), ContractError::InvalidCollectionInfo {}),
+            ];
+            for (msg, expected) in cases {
+                let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), msg).unwrap_err();
+                assert_eq!(err, expected);
+            }
+
+            // Symbols the policy rejects are not reported as available
+            for symbol in ["DR", "DRAKES1", "DRAKE3", "XION"] {
+                let res: SymbolAvailabilityResponse = from_json(
+                    query(deps.as_ref(), factory_env(), QueryMsg::IsSymbolAvailable { symbol: symbol.to_string() }).unwrap()
+                ).unwrap();
+                assert!(!res.valid && !res.available && !res.taken, "{}", symbol);
+            }
+            execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), create("Drake", "DRAKE", "ipfs://meta")).unwrap();
+
+            let config: ConfigResponse = from_json(query(deps.as_ref(), factory_env(), QueryMsg::Config {}).unwrap()).unwrap();
+            assert_eq!(config.validation, policy);
+        }
+
+        #[test]
+        fn test_symbol_reservations() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let env = factory_env();
+            let reserve = |symbol: &str, artist: &str, expires: Expiration| ExecuteMsg::ReserveSymbol {
+                symbol: symbol.to_string(),
+                artist: addr(artist).to_string(),
+                expires,
+            };
+            let availability = |deps: &OwnedDeps<MockStorage, MockApiBech32, MockQuerier>, env: &Env, symbol: &str| {
+                let res: SymbolAvailabilityResponse = from_json(
+                    query(deps.as_ref(), env.clone(), QueryMsg::IsSymbolAvailable { symbol: symbol.to_string() }).unwrap()
+                ).unwrap();
+                res
+            };
+            let expires = Expiration::AtHeight(env.block.height + 100);
+
+            let err = execute(deps.as_mut(), env.clone(), mock_info(ARTIST, &[]), reserve("DRAKE", ARTIST, expires)).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(OWNER, &[]),
+                reserve("DRAKE", ARTIST, Expiration::AtHeight(env.block.height)),
+            ).unwrap_err();
+            assert_eq!(err, ContractError::InvalidReservationExpiry {});
+            let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), reserve("drake", ARTIST, expires)).unwrap_err();
+            assert_eq!(err, ContractError::InvalidSymbolCharacters {});
+
+            execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), reserve("DRAKE", ARTIST, expires)).unwrap();
+            assert_eq!(availability(&deps, &env, "DRAKE"), SymbolAvailabilityResponse {
+                symbol: "DRAKE".to_string(),
+                available: false,
+                valid: true,
+                taken: false,
+                reserved_by: Some(addr(ARTIST)),
+                reserved_until: Some(expires),
+            });
+            let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), reserve("DRAKE", "other", expires)).unwrap_err();
+            assert_eq!(err, ContractError::SymbolReserved { artist: addr(ARTIST).to_string() });
+
+            // Only the artist's collection can use the symbol
+            let msg = create_collection_msg("Drake Collection".to_string(), "DRAKE".to_string(), addr("other"), addr(MINTER));
+            let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), msg).unwrap_err();
+            assert_eq!(err, ContractError::SymbolReserved { artist: addr(ARTIST).to_string() });
+
+            // Expired reservations no longer hold the symbol
+            let mut later = env.clone();
+            later.block.height += 100;
+            assert!(availability(&deps, &later, "DRAKE").available);
+            let msg = create_collection_msg("Drake Collection".to_string(), "DRAKE".to_string(), addr("other"), addr(MINTER));
+            execute(deps.as_mut(), later.clone(), mock_info(OWNER, &[]), msg).unwrap();
+            let availability = availability(&deps, &later, "DRAKE");
+            assert!(availability.taken && !availability.available);
+            let err = execute(deps.as_mut(), later.clone(), mock_info(OWNER, &[]), reserve("DRAKE", ARTIST, Expiration::Never {})).unwrap_err();
+            assert_eq!(err, ContractError::SymbolAlreadyTaken {});
+
+            // Released by the artist or the admin, nobody else
+            execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), reserve("WIZ", ARTIST, expires)).unwrap();
+            let release = ExecuteMsg::ReleaseReservation { symbol: "WIZ".to_string() };
+            let err = execute(deps.as_mut(), env.clone(), mock_info(addr("other").as_str(), &[]), release.clone()).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+            execute(deps.as_mut(), env.clone(), mock_info(addr(ARTIST).as_str(), &[]), release.clone()).unwrap();
+            let err = execute(deps.as_mut(), env.clone(), mock_info(OWNER, &[]), release).unwrap_err();
+            assert_eq!(err, ContractError::ReservationNotFound {});
+        }
+
+        #[test]
+        fn test_update_config() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            let treasury = addr("treasury");
+
+            let update = ExecuteMsg::UpdateConfig {
+                price: Some(25),
+                duration: None,
+                grace_period: Some(GRACE_PERIOD),
+                payment_address: Some(treasury.to_string()),
+            };
+            let err = execute(deps.as_mut(), factory_env(), mock_info(ARTIST, &[]), update.clone()).unwrap_err();
+            assert_eq!(err, ContractError::MissingRole { role: Role::Treasury });
+
+            // Only changed fields show up in the diff
+            let res = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), update).unwrap();
+            let attr = |key: &str| res.attributes.iter().find(|a| a.key == key).map(|a| a.value.clone());
+            assert_eq!(attr("old_price"), Some(PASS_PRICE.to_string()));
+            assert_eq!(attr("new_price"), Some("25".to_string()));
+            assert_eq!(attr("old_payment_address"), Some(addr("payment").to_string()));
+            assert_eq!(attr("new_payment_address"), Some(treasury.to_string()));
+            assert_eq!(attr("new_grace_period"), None);
+            assert_eq!(attr("new_duration"), None);
+
+            let config: ConfigResponse = from_json(query(deps.as_ref(), factory_env(), QueryMsg::Config {}).unwrap()).unwrap();
+            assert_eq!(config.price, 25);
+            assert_eq!(config.duration, PASS_DURATION);
+            assert_eq!(config.payment_address, treasury);
+
+            let invalid = [
+                (Some(0), None, ContractError::InvalidPassDuration {}),
+                (Some(3600), None, ContractError::InvalidGracePeriod {}),
+                (None, Some(PASS_DURATION + 1), ContractError::InvalidGracePeriod {}),
+            ];
+            for (duration, grace_period, expected) in invalid {
+                let err = execute(
+                    deps.as_mut(),
+                    factory_env(),
+                    mock_info(OWNER, &[]),
+                    ExecuteMsg::UpdateConfig { price: None, duration, grace_period, payment_address: None },
+                ).unwrap_err();
+                assert_eq!(err, expected);
+            }
+
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                ExecuteMsg::UpdateConfig { price: None, duration: None, grace_period: None, payment_address: Some("not-bech32".to_string()) },
+            ).unwrap_err();
+            assert!(matches!(err, ContractError::Std(_)));
+        }
+
+        #[test]
+        fn test_sync_collections_validation() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+
+            let sync = |symbols: Vec<String>, fields: Vec<SyncField>| ExecuteMsg::SyncCollections {
+                target: SyncTarget::Symbols { symbols },
+                fields,
+            };
+
+            let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), sync(vec![], vec![])).unwrap_err();
+            assert_eq!(err, ContractError::NoFieldsToSync {});
+
+            let symbols = (0..31).map(|i| format!("S{}", i)).collect();
+            let err = execute(deps.as_mut(), factory_env(), mock_info(OWNER, &[]), sync(symbols, vec![SyncField::Price])).unwrap_err();
+            assert_eq!(err, ContractError::TooManyCollections { max: 30 });
+
+            let err = execute(
+                deps.as_mut(),
+                factory_env(),
+                mock_info(OWNER, &[]),
+                sync(vec!["MISSING".to_string()], vec![SyncField::Price]),
+            ).unwrap_err();
+            assert_eq!(err, ContractError::CollectionNotFound {});
+        }
+
+        #[test]
+        fn test_collection_pagination_and_filters() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+
+            let terms = CollectionTerms {
+                pass_price: PASS_PRICE,
+                pass_denom: "uxion".to_string(),
+                pass_duration: PASS_DURATION,
+                grace_period: GRACE_PERIOD,
+                house_percentage: HOUSE_ROYALTY,
+                artist_percentage: ARTIST_ROYALTY,
+            };
+            // Symbols A to L; every third belongs to another artist, created an hour apart
+            for (i, symbol) in ('A'..='L').enumerate() {
+                let artist = if i % 3 == 0 { addr("other") } else { addr(ARTIST) };
+                let mut collection = Collection::new(
+                    format!("{} Collection", symbol),
+                    symbol.to_string(),
+                    artist,
+                    addr(MINTER),
+                    addr(&format!("contract{}", i)),
+                    i as u64 * 3600,
+                    "Test Collection Metadata".to_string(),
+                    terms.clone(),
+                );
+                if symbol == 'B' {
+                    collection.status = CollectionStatus::Inactive;
+                }
+                save_new_collection(deps.as_mut().storage, &collection).unwrap();
+            }
+
+            let all = |deps: &OwnedDeps<MockStorage, MockApiBech32, MockQuerier>,
+                       start_after: Option<&str>,
+                       limit: u32,
+                       order: Option<SortOrder>,
+                       filter: Option<CollectionFilter>| {
+                let res: CollectionsResponse = from_json(
+                    query(
+                        deps.as_ref(),
+                        factory_env(),
+                        QueryMsg::AllCollections {
+                            start_after: start_after.map(str::to_string),
+                            limit: Some(limit),
+                            order,
+                            filter,
+                            include_inactive: None,
+                        },
+                    ).unwrap()
+                ).unwrap();
+                res
+            };
+            let symbols = |res: &CollectionsResponse| res.collections.iter().map(|c| c.symbol.clone()).collect::<Vec<_>>();
+
+            // Walk every active collection with the cursor
+            let page = all(&deps, None, 5, None, None);
+            assert_eq!(symbols(&page), vec!["A", "C", "D", "E", "F"]);
+            let page = all(&deps, page.next.as_deref(), 5, None, None);
+            assert_eq!(symbols(&page), vec!["G", "H", "I", "J", "K"]);
+            let page = all(&deps, page.next.as_deref(), 5, None, None);
+            assert_eq!(symbols(&page), vec!["L"]);
+            assert_eq!(page.next, None);
+
+            // Limits are capped
+            assert_eq!(all(&deps, None, 1000, None, None).collections.len(), 11);
+
+            let page = all(&deps, Some("D"), 2, Some(SortOrder::Descending), None);
+            assert_eq!(symbols(&page), vec!["C", "A"]);
+
+            let filter = CollectionFilter {
+                artist: Some(addr("other").to_string()),
+                created_after: Some(3 * 3600),
+                created_before: Some(9 * 3600),
+                ..Default::default()
+            };
+            assert_eq!(symbols(&all(&deps, None, 10, None, Some(filter))), vec!["D", "G"]);
+
+            let filter = CollectionFilter { status: Some(CollectionStatus::Inactive), ..Default::default() };
+            assert_eq!(symbols(&all(&deps, None, 10, None, Some(filter))), vec!["B"]);
+
+            let filter = CollectionFilter { minter: Some(addr("nobody").to_string()), ..Default::default() };
+            assert!(all(&deps, None, 10, None, Some(filter)).collections.is_empty());
+
+            // Artist collections page the same way and include inactive ones
+            let res: CollectionsResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::ArtistCollections {
+                        artist: addr(ARTIST).to_string(),
+                        start_after: Some("B".to_string()),
+                        limit: Some(3),
+                        order: None,
+                        filter: None,
+                    },
+                ).unwrap()
+            ).unwrap();
+            assert_eq!(symbols(&res), vec!["C", "E", "F"]);
+            assert_eq!(res.next, Some("F".to_string()));
+
+            let res: CollectionResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::CollectionByAddress { contract_address: addr("contract4").to_string() },
+                ).unwrap()
+            ).unwrap();
+            assert_eq!(res.collection.unwrap().symbol, "E");
+            let res: CollectionResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::CollectionByAddress { contract_address: addr("unknown").to_string() },
+                ).unwrap()
+            ).unwrap();
+            assert!(res.collection.is_none());
+
+            // Removing a collection drops it from every index
+            remove_collection(deps.as_mut().storage, "E").unwrap();
+            let res: CollectionResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::CollectionByAddress { contract_address: addr("contract4").to_string() },
+                ).unwrap()
+            ).unwrap();
+            assert!(res.collection.is_none());
+            let filter = CollectionFilter { minter: Some(addr(MINTER).to_string()), ..Default::default() };
+            assert_eq!(all(&deps, None, 30, None, Some(filter)).collections.len(), 10);
+        }
+
+        #[test]
+        fn test_collection_pages_scan_bounded() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+
+            let terms = CollectionTerms {
+                pass_price: PASS_PRICE,
+                pass_denom: "uxion".to_string(),
+                pass_duration: PASS_DURATION,
+                grace_period: GRACE_PERIOD,
+                house_percentage: HOUSE_ROYALTY,
+                artist_percentage: ARTIST_ROYALTY,
+            };
+            // Only the last of more than a scan's worth of collections is active
+            for i in 0..=300 {
+                let symbol = format!("S{:03}", i);
+                let mut collection = Collection::new(
+                    format!("{} Collection", symbol),
+                    symbol,
+                    addr(ARTIST),
+                    addr(MINTER),
+                    addr(&format!("contract{}", i)),
+                    0,
+                    "Test Collection Metadata".to_string(),
+                    terms.clone(),
+                );
+                if i < 300 {
+                    collection.status = CollectionStatus::Inactive;
+                }
+                save_new_collection(deps.as_mut().storage, &collection).unwrap();
+            }
+
+            let all = |start_after: Option<String>| {
+                let res: CollectionsResponse = from_json(
+                    query(
+                        deps.as_ref(),
+                        factory_env(),
+                        QueryMsg::AllCollections {
+                            start_after,
+                            limit: None,
+                            order: None,
+                            filter: None,
+                            include_inactive: None,
+                        },
+                    ).unwrap()
+                ).unwrap();
+                res
+            };
+
+            // The first page stops after the scan limit without a match
+            let page = all(None);
+            assert!(page.collections.is_empty());
+            assert_eq!(page.next, Some("S299".to_string()));
+
+            let page = all(page.next);
+            assert_eq!(page.collections.len(), 1);
+            assert_eq!(page.collections[0].symbol, "S300");
+            assert_eq!(page.next, None);
+        }
+
+        #[test]
+        fn test_collection_lookups() {
+            let mut deps = mock_deps();
+            setup_contract(deps.as_mut());
+            for symbol in ["ALPHA", "BETA"] {
+                let collection = Collection::new(
+                    format!("{} Collection", symbol),
+                    symbol.to_string(),
+                    addr(ARTIST),
+                    addr(MINTER),
+                    addr(symbol),
+                    0,
+                    "Test Collection Metadata".to_string(),
+                    CollectionTerms {
+                        pass_price: PASS_PRICE,
+                        pass_denom: "uxion".to_string(),
+                        pass_duration: PASS_DURATION,
+                        grace_period: GRACE_PERIOD,
+                        house_percentage: HOUSE_ROYALTY,
+                        artist_percentage: ARTIST_ROYALTY,
+                    },
+                );
+                save_new_collection(deps.as_mut().storage, &collection).unwrap();
+            }
+
+            // No pass_nft behind the mock addresses, the failed stats query is not hidden
+            let err = query(deps.as_ref(), factory_env(), QueryMsg::CollectionBySymbol { symbol: "BETA".to_string() })
+                .unwrap_err();
+            assert!(err.to_string().contains("No such contract"));
+
+            let res: CollectionDetailResponse = from_json(
+                query(deps.as_ref(), factory_env(), QueryMsg::CollectionBySymbol { symbol: "GAMMA".to_string() }).unwrap()
+            ).unwrap();
+            assert!(res.collection.is_none());
+
+            let res: CollectionDetailsResponse = from_json(
+                query(
+                    deps.as_ref(),
+                    factory_env(),
+                    QueryMsg::CollectionsByMinter { minter: addr("other").to_string(), start_after: None, limit: None },
+                ).unwrap()
+            ).unwrap();
+            assert!(res.collections.is_empty());
+            assert_eq!(res.next, None);
+
+            let err = query(
+                deps.as_ref(),
+                factory_env(),
+                QueryMsg::Collections { symbols: vec!["ALPHA".to_string(); 31] },
+            ).unwrap_err();
+            assert_eq!(err, StdError::generic_err("Cannot query more than 30 collections"));
+        }
     }
-}
\ No newline at end of file
+}
@@ -17,4 +17,7 @@ pub enum ContractError {
 
     #[error("Approval not found for: {spender}")]
     ApprovalNotFound { spender: String },
+
+    #[error("This contract does not accept CW20 tokens")]
+    Cw20NotAccepted {},
 }
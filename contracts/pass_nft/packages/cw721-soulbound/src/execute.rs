@@ -63,6 +63,7 @@ where
             ExecuteMsg::RevokeAll { operator } => self.revoke_all(deps, env, info, operator),
             ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
             ExecuteMsg::Extension { msg: _ } => Ok(Response::default()),
+            ExecuteMsg::Receive(_) => Err(ContractError::Cw20NotAccepted {}),
         }
     }
 }
@@ -1,6 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use cw20::Cw20ReceiveMsg;
 use cw721_soulbound::Expiration;
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -54,6 +55,9 @@ pub enum ExecuteMsg<T, E> {
 
     /// Extension msg
     Extension { msg: E },
+
+    /// CW20 tokens sent to this contract, for contracts that take payment in them
+    Receive(Cw20ReceiveMsg),
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1,5 +1,5 @@
 use cosmwasm_schema::write_api;
-use  pass_nft::msg::{ExecuteMsg, InstantiateMsg, };
+use  pass_nft::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg};
 use pass_nft::schema_types::SchemaQueryMsg;
 
 fn main() {
@@ -7,5 +7,6 @@ fn main() {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: SchemaQueryMsg,
+        migrate: MigrateMsg,
     }
 }
@@ -1,33 +1,47 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, StdError, to_json_binary};
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, PassMsg};
-use crate::state::{CONFIG, Config, TOKEN_ID_COUNTER};
-use crate::execute::{mint_pass, renew_pass, burn_expired_pass};
-use crate::query::{query_config, query_validity, query_artist_info, get_user_pass};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, PassMsg};
+use crate::state::{default_revenue_split, migrate_state, CONFIG, Config, DEFAULT_DENOM, PayoutMode, TOKEN_ID_COUNTER};
+use crate::execute::{
+    mint_pass, renew_pass, burn_expired_pass, update_config, set_paused, withdraw, receive_cw20, change_tier,
+    set_tier, remove_tier, set_mint_limits,
+};
+use crate::query::{
+    query_config, query_validity, query_artist_info, get_user_pass, query_claimable_balance, query_tiers,
+    query_supply,
+};
 use crate::msg::PassQuery;
 use crate::state::Contract;
-use crate::helpers::convert_query_msg;
+use crate::helpers::{
+    convert_query_msg, validate_additional_prices, validate_cw20_prices, validate_denom, validate_pass_terms, validate_prepaid_limit,
+    validate_revenue_split,
+};
 
 // Version info for migration info
 const CONTRACT_NAME: &str = "crates.io:loop_music";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Collections instantiated before the version was written after the base
+// contract carry the cw721 base name and version in cw2
+const LEGACY_CONTRACT_NAME: &str = "crates.io:cw721-soulbound";
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
 
-    let payment_address = deps.api.addr_validate(&msg.payment_address.to_string())?;
-    let artist = deps.api.addr_validate(&msg.artist.to_string())?;
+    let payment_address = deps.api.addr_validate(msg.payment_address.as_ref())?;
+    let artist = deps.api.addr_validate(msg.artist.as_ref())?;
 
     let collection_name = msg.name;
     let collection_symbol = msg.symbol;
@@ -35,11 +49,23 @@ pub fn instantiate(
     if msg.house_percentage + msg.artist_percentage != 100 {
         return Err(ContractError::InvalidRoyalties {});
     }
-
-    let config = Config {
+    let revenue_split = msg
+        .revenue_split
+        .unwrap_or_else(|| default_revenue_split(msg.house_percentage, msg.artist_percentage));
+    validate_revenue_split(deps.api, &revenue_split)?;
+    validate_pass_terms(msg.pass_duration, msg.grace_period)?;
+
+    let pass_denom = msg.pass_denom.unwrap_or_else(|| DEFAULT_DENOM.to_string());
+    validate_denom(&pass_denom)?;
+    let additional_prices = msg.additional_prices.unwrap_or_default();
+    validate_additional_prices(&pass_denom, &additional_prices)?;
+    let cw20_prices = validate_cw20_prices(deps.api, msg.cw20_prices.unwrap_or_default())?;
+
+    let mut config = Config {
         name: collection_name.clone(),
         symbol: collection_symbol.clone(),
         pass_price: msg.pass_price,
+        pass_denom,
         collection_info: msg.collection_info,
         minter: msg.minter,
         pass_duration: msg.pass_duration,
@@ -48,10 +74,20 @@ pub fn instantiate(
         artist: artist.clone(),
         house_percentage: msg.house_percentage,
         artist_percentage: msg.artist_percentage,
-        
+        paused: false,
+        revenue_split: vec![],
+        payout_mode: PayoutMode::Push,
+        additional_prices,
+        cw20_prices,
+        payment_policy: msg.payment_policy.unwrap_or_default(),
+        max_prepaid_duration: msg.max_prepaid_duration,
+        max_supply: msg.max_supply,
+        max_per_wallet: msg.max_per_wallet,
+        holder_policy: msg.holder_policy.unwrap_or_default(),
     };
+    validate_prepaid_limit(deps.storage, &config)?;
+    config.set_revenue_split(revenue_split);
 
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     CONFIG.save(deps.storage, &config)?;
 
      // Initialize token ID counter
@@ -64,7 +100,10 @@ pub fn instantiate(
         symbol: config.symbol,
         minter: config.minter.to_string(),
     };
-    contract.instantiate(deps, env, info, cw721_msg)?;
+    contract.instantiate(deps.branch(), env, info, cw721_msg)?;
+
+    // Set after the base contract, which writes its own cw2 version
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
       
 
@@ -74,8 +113,8 @@ pub fn instantiate(
         .add_attribute("collection_symbol", collection_symbol)
         .add_attribute("artist", artist)
         .add_attribute("payment_address", payment_address)
-        .add_attribute("house_percentage", msg.house_percentage.to_string())
-        .add_attribute("artist_percentage", msg.artist_percentage.to_string()))
+        .add_attribute("house_percentage", config.house_percentage.to_string())
+        .add_attribute("artist_percentage", config.artist_percentage.to_string()))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -87,13 +126,22 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Extension { msg } => match msg {
-            PassMsg::MintPass { owner_address }
+            PassMsg::MintPass { owner_address, tier }
              => {
                 deps.api.debug("Executing mint_pass");
-                mint_pass(deps, env, info, owner_address)},
-            PassMsg::RenewPass { token_id } => renew_pass(deps, env, info, token_id),
+                mint_pass(deps, env, info, owner_address, tier)},
+            PassMsg::RenewPass { token_id, periods } => renew_pass(deps, env, info, token_id, periods),
             PassMsg::BurnExpiredPass { token_id } => burn_expired_pass(deps, env, info, token_id),
+            PassMsg::UpdateConfig(update) => update_config(deps, env, info, update),
+            PassMsg::ChangeTier { token_id, tier } => change_tier(deps, env, info, token_id, tier),
+            PassMsg::SetTier { name, tier } => set_tier(deps, env, info, name, tier),
+            PassMsg::RemoveTier { name } => remove_tier(deps, env, info, name),
+            PassMsg::SetMintLimits { max_supply, max_per_wallet } =>
+                set_mint_limits(deps, env, info, max_supply, max_per_wallet),
+            PassMsg::SetPaused { paused } => set_paused(deps, env, info, paused),
+            PassMsg::Withdraw { denom, amount } => withdraw(deps, info, denom, amount),
         },
+        ExecuteMsg::Receive(wrapper) => receive_cw20(deps, env, info, wrapper),
         _ => Err(ContractError::Custom("Unsupported operation".to_string())),
     }
 }
@@ -111,7 +159,10 @@ pub fn query(
         QueryMsg::Extension { msg } => match msg {
             PassQuery::CheckValidity { token_id } => to_json_binary(&query_validity(deps, env, token_id)?),
             PassQuery::GetConfig {} => to_json_binary(&query_config(deps)?),
-            PassQuery::GetArtistInfo {} => to_json_binary(&query_artist_info(deps)?),
+            PassQuery::GetArtistInfo {} => to_json_binary(&query_artist_info(deps, env)?),
+            PassQuery::ClaimableBalance { address } => to_json_binary(&query_claimable_balance(deps, address)?),
+            PassQuery::Tiers {} => to_json_binary(&query_tiers(deps)?),
+            PassQuery::Supply {} => to_json_binary(&query_supply(deps)?),
             PassQuery::GetUserPass { symbol, owner } => { 
                 to_json_binary(&get_user_pass(deps, env, symbol, owner)?)
             }
@@ -124,3 +175,38 @@ pub fn query(
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    let stored_version = match stored.contract.as_str() {
+        CONTRACT_NAME => {
+            // Refuse to run an older binary over newer state
+            let stored_version: Version = stored.version.parse()?;
+            let current_version: Version = CONTRACT_VERSION.parse()?;
+            if stored_version > current_version {
+                return Err(ContractError::MigrationDowngrade {
+                    stored: stored.version,
+                    current: CONTRACT_VERSION.to_string(),
+                });
+            }
+            stored_version
+        }
+        // The stored version is the base contract's, so the state predates
+        // every pass_nft version
+        LEGACY_CONTRACT_NAME => Version::new(0, 0, 0),
+        _ => {
+            return Err(ContractError::InvalidMigrationContract {
+                expected: CONTRACT_NAME.to_string(),
+                found: stored.contract,
+            })
+        }
+    };
+
+    migrate_state(deps.storage, &stored_version)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
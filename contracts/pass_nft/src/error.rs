@@ -20,12 +20,30 @@ pub enum ContractError {
     Custom(String),
 
     // Payment errors
-    #[error("Insufficient payment - expected {expected} uxion, got {received} uxion")]
-    InsufficientPayment { expected: u128, received: u128 },
+    #[error("Insufficient payment - expected {expected} {denom}, got {received} {denom}")]
+    InsufficientPayment { expected: u128, received: u128, denom: String },
+
+    #[error("Payment must be exactly {expected} {denom}, got {received} {denom}")]
+    InexactPayment { expected: u128, received: u128, denom: String },
 
     #[error("No payment sent with transaction")]
     NoPayment {},
 
+    #[error("Send exactly one coin to pay for a pass")]
+    MixedDenoms {},
+
+    #[error("Denom {denom} is not accepted by this collection")]
+    UnsupportedDenom { denom: String },
+
+    #[error("Invalid denom {denom}")]
+    InvalidDenom { denom: String },
+
+    #[error("Denom {denom} is priced more than once")]
+    DuplicateDenom { denom: String },
+
+    #[error("Insufficient balance - {available} {denom} available")]
+    InsufficientBalance { available: u128, denom: String },
+
     // Pass status errors
     #[error("Pass is not expired yet")]
     PassStillValid {},
@@ -33,24 +51,85 @@ pub enum ContractError {
     #[error("Invalid royalties")]
     InvalidRoyalties {},
 
+    #[error("Revenue split must add up to 10000 bps, with an address for every role but house and artist")]
+    InvalidRevenueSplit {},
+
     #[error("Pass has expired and grace period has ended")]
     PassExpiredAndGracePeriodEnded {},
 
     #[error("Pass is in grace period and can still be renewed")]
     PassInGracePeriod {},
 
+    #[error("A pass must be renewed for at least one period")]
+    InvalidRenewalPeriods {},
+
+    #[error("Passes cannot be paid for more than {max_prepaid_duration} seconds ahead")]
+    PrepaidLimitExceeded { max_prepaid_duration: u64 },
+
+    #[error("Prepaid limit must cover at least one pass duration, on every tier")]
+    InvalidPrepaidLimit {},
+
+    #[error("Tier {tier} does not exist")]
+    TierNotFound { tier: String },
+
+    #[error("Tier {tier} is sold out")]
+    TierSoldOut { tier: String },
+
+    #[error("Tier {tier} still has passes on it")]
+    TierInUse { tier: String },
+
+    #[error("Pass duration must be greater than zero")]
+    InvalidPassDuration {},
+
+    #[error("Grace period cannot be longer than the pass duration")]
+    InvalidGracePeriod {},
+
+    #[error("Tiers need a name and a duration greater than zero")]
+    InvalidTier {},
+
+    #[error("Pass is already on this tier")]
+    SameTier {},
+
+    #[error("Owner already holds pass {token_id}")]
+    OwnerHasPass { token_id: String },
+
     #[error("Pass does not exist")]
     PassNotFound {},
 
+    #[error("Collection is paused")]
+    CollectionPaused {},
+
     // Supply limit error
     #[error("Maximum token supply reached")]
     MaxSupplyReached {},
 
+    #[error("Owner already holds the maximum of {max_per_wallet} passes")]
+    WalletLimitReached { max_per_wallet: u32 },
+
+    #[error("Maximum supply cannot be below the {minted} passes already minted")]
+    MaxSupplyBelowMinted { minted: u64 },
+
     // Base contract operation errors
     #[error("Direct minting not allowed - use MintPass instead")]
     DirectMintNotAllowed {},
 
     #[error("Pass cannot be transferred - soulbound NFT")]
     NoTransfer {},
+
+    // Migration errors
+    #[error("Cannot migrate from contract {found}, expected {expected}")]
+    InvalidMigrationContract { expected: String, found: String },
+
+    #[error("Cannot migrate from version {stored} down to {current}")]
+    MigrationDowngrade { stored: String, current: String },
+
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
 
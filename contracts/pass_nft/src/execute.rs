@@ -1,16 +1,22 @@
-
-#[cfg(not(feature = "library"))]
-
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{
+    from_json, Addr, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
 use cw721_base_soulbound::state::TokenInfo;
-use cosmwasm_std::{Coin, Uint128, BankMsg};
 
 // use cw721_base_soulbound::ExecuteMsg::Mint;
 
 use crate::error::ContractError;
-use crate::state::{Contract, PassExtension, CONFIG, TOKEN_ID_COUNTER};
+use crate::msg::{ConfigUpdate, ReceiveMsg};
+use crate::state::{
+    default_revenue_split, index_expiry, Config, Contract, HolderPolicy, PassExtension, Tier, BALANCES, CONFIG, PASS_BY_OWNER, TIERS, TIER_SUPPLY, TOKEN_ID_COUNTER,
+};
 use crate::state::PassStatus;
-use crate::helpers::validate_payment;
+use crate::helpers::{
+    check_payment, ensure_collection_admin, ensure_wasm_admin, distribute_revenue, owner_pass, paid_coin, payout_msg,
+    remaining_value, value_time, validate_additional_prices, validate_cw20_payment, validate_cw20_prices,
+    validate_pass_terms, validate_payment, validate_prepaid_limit, validate_revenue_split, validate_tier, Payment,
+};
 // use crate::msg::{ExecuteMsg, PassMsg};
 
 
@@ -19,13 +25,123 @@ pub fn mint_pass(
     env: Env,
     info: MessageInfo,
     owner_address: String,
+    tier: Option<String>,
 ) -> Result<Response, ContractError> {
 
     let config = CONFIG.load(deps.storage)?;
+    if config.paused {
+        return Err(ContractError::CollectionPaused {});
+    }
+    let sender = info.sender.clone();
+
+    mint_or_renew(deps, env, config, sender, owner_address, tier, |config, tier| {
+        // Validate payment
+        validate_payment(&info, config, tier, 1)
+    })
+}
+
+/// Mints a pass to `owner_address`, unless the owner already holds an
+/// active pass and the holder policy rejects the mint or turns it into a
+/// renewal. The payment is taken from `pay` once the tier is known.
+fn mint_or_renew(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: Addr,
+    owner_address: String,
+    tier: Option<String>,
+    pay: impl FnOnce(&Config, Option<&Tier>) -> Result<Payment, ContractError>,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&owner_address)?;
+    if config.holder_policy != HolderPolicy::Allow {
+        let held = owner_pass(deps.storage, &owner)?
+            .filter(|(_, token)| token.extension.status(env.block.time) != PassStatus::Expired);
+        if let Some((token_id, token)) = held {
+            // A renewal keeps the pass on its tier, so asking for another one is a second pass
+            if config.holder_policy == HolderPolicy::Reject || token.extension.tier != tier {
+                return Err(ContractError::OwnerHasPass { token_id });
+            }
+            // Anyone can mint to the owner, so anyone can pay the renewal
+            return extend_pass(deps, env, config, sender, token_id, 1, pay);
+        }
+    }
+
+    let tier = load_tier(deps.storage, tier)?;
+    let payment = pay(&config, tier_of(&tier))?;
+    issue_pass(deps, env, config, sender, owner, tier, payment)
+}
+
+/// Loads a tier by name, `None` standing for the collection's own terms
+fn load_tier(storage: &dyn Storage, name: Option<String>) -> Result<Option<(String, Tier)>, ContractError> {
+    name.map(|name| match TIERS.may_load(storage, &name)? {
+        Some(tier) => Ok((name, tier)),
+        None => Err(ContractError::TierNotFound { tier: name }),
+    })
+    .transpose()
+}
+
+fn tier_of(tier: &Option<(String, Tier)>) -> Option<&Tier> {
+    tier.as_ref().map(|(_, tier)| tier)
+}
+
+/// Counts a new pass on a tier, up to its supply cap
+fn take_tier_slot(storage: &mut dyn Storage, tier: &Option<(String, Tier)>) -> Result<(), ContractError> {
+    if let Some((name, tier)) = tier {
+        let supply = TIER_SUPPLY.may_load(storage, name)?.unwrap_or_default();
+        if tier.max_supply.is_some_and(|max_supply| supply >= max_supply) {
+            return Err(ContractError::TierSoldOut { tier: name.clone() });
+        }
+        TIER_SUPPLY.save(storage, name, &(supply + 1))?;
+    }
+    Ok(())
+}
+
+/// Stops counting a pass that left a tier
+fn release_tier_slot(storage: &mut dyn Storage, tier: Option<&str>) -> StdResult<()> {
+    if let Some(name) = tier {
+        let supply = TIER_SUPPLY.may_load(storage, name)?.unwrap_or_default();
+        TIER_SUPPLY.save(storage, name, &supply.saturating_sub(1))?;
+    }
+    Ok(())
+}
+
+/// Checks one more pass fits the collection's supply and the owner's limit
+fn check_mint_limits(deps: Deps, config: &Config, owner: &Addr) -> Result<(), ContractError> {
+    let contract = Contract::default();
+    if let Some(max_supply) = config.max_supply {
+        if TOKEN_ID_COUNTER.load(deps.storage)? >= max_supply {
+            return Err(ContractError::MaxSupplyReached {});
+        }
+    }
+    if let Some(max_per_wallet) = config.max_per_wallet {
+        let held = contract
+            .tokens
+            .idx
+            .owner
+            .prefix(owner.clone())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .count();
+        if held >= max_per_wallet as usize {
+            return Err(ContractError::WalletLimitReached { max_per_wallet });
+        }
+    }
+    Ok(())
+}
+
+/// Mints a pass to `owner` for a payment already checked against
+/// the price, and pays the payment out along the revenue split.
+fn issue_pass(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    minter: Addr,
+    owner: Addr,
+    tier: Option<(String, Tier)>,
+    payment: Payment,
+) -> Result<Response, ContractError> {
+    check_mint_limits(deps.as_ref(), &config, &owner)?;
+    take_tier_slot(deps.storage, &tier)?;
 
-    // Validate payment
-    validate_payment(&info, config.pass_price)?;
- 
     // Get and increment token ID
     let current_token_id = TOKEN_ID_COUNTER.load(deps.storage)?;
 
@@ -36,10 +152,12 @@ pub fn mint_pass(
     let token_id = format!("{}-{}", config.symbol.to_lowercase(), next_id);
   
     // Create new pass extension
+    let (pass_duration, grace_period) = config.tier_terms(tier_of(&tier));
     let extension = PassExtension::new(
         env.block.time,
-        config.pass_duration,
-        config.grace_period,
+        pass_duration,
+        grace_period,
+        tier.map(|(name, _)| name),
     );
 
     let contract = Contract::default();
@@ -52,52 +170,32 @@ pub fn mint_pass(
 
     // Create token directly
     let token = TokenInfo {
-        owner: deps.api.addr_validate(&owner_address)?,
+        owner,
         approvals: vec![],
-        token_uri: Some(config.collection_info),
+        token_uri: Some(config.collection_info.clone()),
         extension,
     };
 
     // Save token directly
     contract.tokens.save(deps.storage, &token_id, &token)?;
+    PASS_BY_OWNER.save(deps.storage, &token.owner, &token_id)?;
+    index_expiry(deps.storage, &token_id, None, Some(token.extension.expires_at))?;
   
     // Increment token count
     contract.increment_tokens(deps.storage)?;
    
-    // v.0.1.0 create the royalty split code here. 70% for artist and 30% for loop.
-
-    let payment = info.funds[0].amount.u128();
-
-    let house_amount = (payment * config.house_percentage as u128) / 100u128;
-
-    let artist_amount = payment - house_amount;
-
-    // Create royalty split payment message
-    let house_payment_msg =  BankMsg::Send {
-        to_address: config.payment_address.to_string(),
-        amount: vec![Coin {
-            denom: info.funds[0].denom.clone(),
-            amount: Uint128::from(house_amount),
-        }],
-    };
-
-    let artist_payment_msg =  BankMsg::Send { 
-        to_address: config.artist.to_string(),
-        amount: vec![Coin {
-            denom: info.funds[0].denom.clone(),
-            amount: Uint128::from(artist_amount),
-        }]
-    };
+    let payouts = settle_payment(deps.storage, &config, &minter, &payment)?;
 
     deps.api.debug("Returning successful response");
     Ok(Response::new()
-        .add_message(house_payment_msg)
-        .add_message(artist_payment_msg)
+        .add_messages(payouts)
         .add_attribute("action", "mint_pass")
         .add_attribute("collection", config.name)
         .add_attribute("artist", config.artist)
-        .add_attribute("minter", info.sender)
-        .add_attribute("token_id", token_id))
+        .add_attribute("minter", minter)
+        .add_attribute("token_id", token_id)
+        .add_attribute("tier", token.extension.tier.unwrap_or_default())
+        .add_attributes(payment_attributes(&payment)))
 }
 
 
@@ -106,65 +204,144 @@ pub fn renew_pass(
     env: Env,
     info: MessageInfo,
     token_id: String,
+    periods: Option<u32>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    
-    // Validate payment
-    validate_payment(&info, config.pass_price)?;
-
-    let contract = Contract::default();
+    if config.paused {
+        return Err(ContractError::CollectionPaused {});
+    }
+    let periods = renewal_periods(periods)?;
+    let sender = info.sender.clone();
+    ensure_renewer(deps.storage, &config, &sender, &token_id)?;
+
+    extend_pass(deps, env, config, sender, token_id, periods, |config, tier| {
+        // Validate payment
+        validate_payment(&info, config, tier, periods)
+    })
+}
 
-    let mut token = contract.tokens.load(deps.storage, &token_id)?;
+fn renewal_periods(periods: Option<u32>) -> Result<u32, ContractError> {
+    match periods.unwrap_or(1) {
+        0 => Err(ContractError::InvalidRenewalPeriods {}),
+        periods => Ok(periods),
+    }
+}
 
-    // Allow both token owner and admin to renew
-    if info.sender != token.owner && info.sender != config.minter {
+/// Only the owner of a pass or the minter can renew it through `RenewPass`
+fn ensure_renewer(storage: &dyn Storage, config: &Config, sender: &Addr, token_id: &str) -> Result<(), ContractError> {
+    let token = Contract::default().tokens.load(storage, token_id)?;
+    if *sender != token.owner && *sender != config.minter {
         return Err(ContractError::Unauthorized {});
     }
+    Ok(())
+}
 
-    // Renew the pass
-    token.extension.renew(
-        env.block.time,
-        config.pass_duration,
-        config.grace_period,
-    );
+/// Renews a pass for `periods` on its tier, taking the payment from `pay`
+/// once the tier is known, and pays it out along the revenue split.
+/// Callers check who may renew.
+fn extend_pass(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: Addr,
+    token_id: String,
+    periods: u32,
+    pay: impl FnOnce(&Config, Option<&Tier>) -> Result<Payment, ContractError>,
+) -> Result<Response, ContractError> {
+    let contract = Contract::default();
 
-    // Save updated token
-    contract.tokens.save(deps.storage, &token_id, &token)?;
+    let mut token = contract.tokens.load(deps.storage, &token_id)?;
 
-    // royalty split after renewal 
+    // Passes past their grace period are burned and minted again instead
+    if token.extension.status(env.block.time) == PassStatus::Expired {
+        return Err(ContractError::PassExpiredAndGracePeriodEnded {});
+    }
 
-    let payment = info.funds[0].amount.u128();
+    let tier = load_tier(deps.storage, token.extension.tier.clone())?;
+    let payment = pay(&config, tier_of(&tier))?;
+    let (pass_duration, grace_period) = config.tier_terms(tier_of(&tier));
 
-    let house_amount = (payment * config.house_percentage as u128) / 100u128;
+    let expires_at = token.extension.renewed_expiry(env.block.time, pass_duration, periods)?;
+    if let Some(max_prepaid_duration) = config.max_prepaid_duration {
+        if expires_at > env.block.time.plus_seconds(max_prepaid_duration) {
+            return Err(ContractError::PrepaidLimitExceeded { max_prepaid_duration });
+        }
+    }
 
-    let artist_amount = payment - house_amount;
+    // Renew the pass
+    index_expiry(deps.storage, &token_id, Some(token.extension.expires_at), Some(expires_at))?;
+    token.extension.renew(expires_at, grace_period);
 
-    // Create royalty split payment message
-    let house_payment_msg =  BankMsg::Send {
-        to_address: config.payment_address.to_string(),
-        amount: vec![Coin {
-            denom: info.funds[0].denom.clone(),
-            amount: Uint128::from(house_amount),
-        }],
-    };
+    // Save updated token
+    contract.tokens.save(deps.storage, &token_id, &token)?;
 
-    let artist_payment_msg =  BankMsg::Send { 
-        to_address: config.artist.to_string(),
-        amount: vec![Coin {
-            denom: info.funds[0].denom.clone(),
-            amount: Uint128::from(artist_amount),
-        }]
-    };
+    let payouts = settle_payment(deps.storage, &config, &sender, &payment)?;
 
     Ok(Response::new()
-        .add_message(house_payment_msg)
-        .add_message(artist_payment_msg)
+        .add_messages(payouts)
         .add_attribute("action", "renew_pass")
         .add_attribute("collection", config.name)
         .add_attribute("artist", config.artist)
         .add_attribute("token_id", token_id) 
         .add_attribute("owner", token.owner.to_string()) 
-        .add_attribute("new_expiry", token.extension.expires_at.to_string()))
+        .add_attribute("tier", token.extension.tier.unwrap_or_default())
+        .add_attribute("periods", periods.to_string())
+        .add_attribute("new_expiry", token.extension.expires_at.to_string())
+        .add_attributes(payment_attributes(&payment)))
+}
+
+/// Pays the price out along the revenue split and refunds any surplus to
+/// the payer.
+fn settle_payment(
+    storage: &mut dyn Storage,
+    config: &Config,
+    payer: &Addr,
+    payment: &Payment,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs = distribute_revenue(storage, config, &payment.denom, payment.price)?;
+    if payment.refund() > 0 {
+        msgs.push(payout_msg(payer, &payment.denom, payment.refund())?);
+    }
+    Ok(msgs)
+}
+
+fn payment_attributes(payment: &Payment) -> [(&'static str, String); 3] {
+    [
+        ("price", format!("{}{}", payment.price, payment.denom)),
+        ("paid", format!("{}{}", payment.paid, payment.denom)),
+        ("refunded", format!("{}{}", payment.refund(), payment.denom)),
+    ]
+}
+
+/// Mints or renews a pass paid for with a CW20 `Send`. The sender is the
+/// token contract, which must be one the collection accepts, and the pass is
+/// minted or renewed on behalf of the account that sent the tokens.
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.paused {
+        return Err(ContractError::CollectionPaused {});
+    }
+
+    let sender = deps.api.addr_validate(&wrapper.sender)?;
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::MintPass { owner_address } => {
+            mint_or_renew(deps, env, config, sender, owner_address, None, |config, tier| {
+                validate_cw20_payment(config, tier, &info.sender, wrapper.amount, 1)
+            })
+        }
+        ReceiveMsg::RenewPass { token_id, periods } => {
+            let periods = renewal_periods(periods)?;
+            ensure_renewer(deps.storage, &config, &sender, &token_id)?;
+            extend_pass(deps, env, config, sender, token_id, periods, |config, tier| {
+                validate_cw20_payment(config, tier, &info.sender, wrapper.amount, periods)
+            })
+        }
+    }
 }
 
 
@@ -192,6 +369,11 @@ pub fn burn_expired_pass(
   
     contract.tokens.remove(deps.storage, &token_id)?;
     contract.decrement_tokens(deps.storage)?;
+    release_tier_slot(deps.storage, token.extension.tier.as_deref())?;
+    index_expiry(deps.storage, &token_id, Some(token.extension.expires_at), None)?;
+    if PASS_BY_OWNER.may_load(deps.storage, &token.owner)?.as_ref() == Some(&token_id) {
+        PASS_BY_OWNER.remove(deps.storage, &token.owner);
+    }
 
     Ok(Response::new()
         .add_attribute("action", "burn_expired_pass")
@@ -199,4 +381,312 @@ pub fn burn_expired_pass(
         .add_attribute("artist", config.artist)
         .add_attribute("token_id", token_id)
         .add_attribute("owner", info.sender))
-}
\ No newline at end of file
+}
+
+pub fn update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let ConfigUpdate {
+        payment_address,
+        pass_price,
+        pass_duration,
+        grace_period,
+        house_percentage,
+        artist_percentage,
+        revenue_split,
+        payout_mode,
+        additional_prices,
+        cw20_prices,
+        payment_policy,
+        max_prepaid_duration,
+        holder_policy,
+    } = update;
+    let mut config = CONFIG.load(deps.storage)?;
+    // Where the revenue goes is up to the factory, the minter only sets the terms
+    let treasury_changed = payment_address.is_some()
+        || house_percentage.is_some()
+        || artist_percentage.is_some()
+        || revenue_split.is_some();
+    if treasury_changed {
+        ensure_wasm_admin(deps.as_ref(), &env, &info.sender)?;
+    } else {
+        ensure_collection_admin(deps.as_ref(), &env, &info.sender, &config)?;
+    }
+
+    if let Some(payment_address) = payment_address {
+        config.payment_address = deps.api.addr_validate(&payment_address)?;
+    }
+    if let Some(pass_price) = pass_price {
+        config.pass_price = pass_price;
+    }
+    if let Some(pass_duration) = pass_duration {
+        config.pass_duration = pass_duration;
+    }
+    if let Some(grace_period) = grace_period {
+        config.grace_period = grace_period;
+    }
+
+    let percentages_changed = house_percentage.is_some() || artist_percentage.is_some();
+    match revenue_split {
+        // A split and percentages would contradict each other
+        Some(_) if percentages_changed => return Err(ContractError::InvalidRoyalties {}),
+        Some(revenue_split) => {
+            validate_revenue_split(deps.api, &revenue_split)?;
+            config.set_revenue_split(revenue_split);
+        }
+        None if percentages_changed => {
+            let house_percentage = house_percentage.unwrap_or(config.house_percentage);
+            let artist_percentage = artist_percentage.unwrap_or(config.artist_percentage);
+            if house_percentage + artist_percentage != 100 {
+                return Err(ContractError::InvalidRoyalties {});
+            }
+            config.set_revenue_split(default_revenue_split(house_percentage, artist_percentage));
+        }
+        None => {}
+    }
+    if let Some(payout_mode) = payout_mode {
+        config.payout_mode = payout_mode;
+    }
+    if let Some(additional_prices) = additional_prices {
+        validate_additional_prices(&config.pass_denom, &additional_prices)?;
+        config.additional_prices = additional_prices;
+    }
+    if let Some(cw20_prices) = cw20_prices {
+        config.cw20_prices = validate_cw20_prices(deps.api, cw20_prices)?;
+    }
+    if let Some(payment_policy) = payment_policy {
+        config.payment_policy = payment_policy;
+    }
+    if let Some(max_prepaid_duration) = max_prepaid_duration {
+        config.max_prepaid_duration = Some(max_prepaid_duration).filter(|max| *max > 0);
+    }
+    if let Some(holder_policy) = holder_policy {
+        config.holder_policy = holder_policy;
+    }
+    validate_pass_terms(config.pass_duration, config.grace_period)?;
+    validate_prepaid_limit(deps.storage, &config)?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_config")
+        .add_attribute("collection", config.name)
+        .add_attribute("updated_by", info.sender)
+        .add_attribute("payment_address", config.payment_address)
+        .add_attribute("pass_price", config.pass_price.to_string())
+        .add_attribute("house_percentage", config.house_percentage.to_string())
+        .add_attribute("artist_percentage", config.artist_percentage.to_string()))
+}
+
+pub fn set_paused(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    ensure_wasm_admin(deps.as_ref(), &env, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.paused = paused;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_paused")
+        .add_attribute("collection", config.name)
+        .add_attribute("paused", paused.to_string()))
+}
+
+/// Moves a pass to another tier. An upgrade keeps the expiry and costs what
+/// the time left is worth on the new tier minus what it is worth on the
+/// current one. A downgrade extends the expiry by the time the difference
+/// buys on the new tier.
+pub fn change_tier(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    tier: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.paused {
+        return Err(ContractError::CollectionPaused {});
+    }
+
+    let contract = Contract::default();
+    let mut token = contract.tokens.load(deps.storage, &token_id)?;
+    if info.sender != token.owner && info.sender != config.minter {
+        return Err(ContractError::Unauthorized {});
+    }
+    // A pass past its expiry has no time left to price, it is renewed first
+    match token.extension.status(env.block.time) {
+        PassStatus::Active => {}
+        PassStatus::InGracePeriod => return Err(ContractError::PassInGracePeriod {}),
+        PassStatus::Expired => return Err(ContractError::PassExpiredAndGracePeriodEnded {}),
+    }
+    if token.extension.tier == tier {
+        return Err(ContractError::SameTier {});
+    }
+
+    let current = load_tier(deps.storage, token.extension.tier.clone())?;
+    let target = load_tier(deps.storage, tier)?;
+    let remaining = token.extension.expires_at.seconds().saturating_sub(env.block.time.seconds());
+    let current_value = remaining_value(&config, tier_of(&current), remaining);
+    let target_value = remaining_value(&config, tier_of(&target), remaining);
+    let cost = target_value.saturating_sub(current_value);
+
+    // A downgrade turns the value left over into more time on the new tier
+    let credit = value_time(&config, tier_of(&target), current_value.saturating_sub(target_value))?;
+    let expires_at = token.extension.expires_at.plus_seconds(credit);
+    if let Some(max_prepaid_duration) = config.max_prepaid_duration {
+        if credit > 0 && expires_at > env.block.time.plus_seconds(max_prepaid_duration) {
+            return Err(ContractError::PrepaidLimitExceeded { max_prepaid_duration });
+        }
+    }
+
+    let payment = if cost == 0 {
+        if !info.funds.is_empty() {
+            return Err(ContractError::Custom("Changing to this tier takes no payment".to_string()));
+        }
+        None
+    } else {
+        let paid = paid_coin(&info)?;
+        if paid.denom != config.pass_denom {
+            return Err(ContractError::UnsupportedDenom { denom: paid.denom.clone() });
+        }
+        Some(check_payment(&config, paid.denom.clone(), cost, paid.amount.u128())?)
+    };
+
+    release_tier_slot(deps.storage, token.extension.tier.as_deref())?;
+    take_tier_slot(deps.storage, &target)?;
+
+    let (_, grace_period) = config.tier_terms(tier_of(&target));
+    let from_tier = token.extension.tier.take().unwrap_or_default();
+    token.extension.tier = target.map(|(name, _)| name);
+    index_expiry(deps.storage, &token_id, Some(token.extension.expires_at), Some(expires_at))?;
+    token.extension.expires_at = expires_at;
+    token.extension.grace_period_end = expires_at.plus_seconds(grace_period);
+    contract.tokens.save(deps.storage, &token_id, &token)?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "change_tier")
+        .add_attribute("collection", &config.name)
+        .add_attribute("token_id", token_id)
+        .add_attribute("from_tier", from_tier)
+        .add_attribute("to_tier", token.extension.tier.unwrap_or_default())
+        .add_attribute("expires_at", expires_at.to_string());
+    if let Some(payment) = payment {
+        res = res
+            .add_messages(settle_payment(deps.storage, &config, &info.sender, &payment)?)
+            .add_attributes(payment_attributes(&payment));
+    }
+    Ok(res)
+}
+
+pub fn set_tier(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    tier: Tier,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_collection_admin(deps.as_ref(), &env, &info.sender, &config)?;
+    validate_tier(&config, &name, &tier)?;
+
+    TIERS.save(deps.storage, &name, &tier)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_tier")
+        .add_attribute("collection", config.name)
+        .add_attribute("tier", name)
+        .add_attribute("price", format!("{}{}", tier.price, config.pass_denom))
+        .add_attribute("duration", tier.duration.to_string()))
+}
+
+pub fn remove_tier(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    ensure_collection_admin(deps.as_ref(), &env, &info.sender, &config)?;
+
+    if !TIERS.has(deps.storage, &name) {
+        return Err(ContractError::TierNotFound { tier: name });
+    }
+    if TIER_SUPPLY.may_load(deps.storage, &name)?.unwrap_or_default() > 0 {
+        return Err(ContractError::TierInUse { tier: name });
+    }
+    TIERS.remove(deps.storage, &name);
+    TIER_SUPPLY.remove(deps.storage, &name);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_tier")
+        .add_attribute("collection", config.name)
+        .add_attribute("tier", name))
+}
+
+pub fn set_mint_limits(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    max_supply: Option<u64>,
+    max_per_wallet: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    ensure_collection_admin(deps.as_ref(), &env, &info.sender, &config)?;
+
+    if let Some(max_supply) = max_supply {
+        let minted = TOKEN_ID_COUNTER.load(deps.storage)?;
+        if max_supply != 0 && max_supply < minted {
+            return Err(ContractError::MaxSupplyBelowMinted { minted });
+        }
+        config.max_supply = Some(max_supply).filter(|max| *max > 0);
+    }
+    if let Some(max_per_wallet) = max_per_wallet {
+        config.max_per_wallet = Some(max_per_wallet).filter(|max| *max > 0);
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    let limit = |limit: Option<String>| limit.unwrap_or_else(|| "unlimited".to_string());
+    Ok(Response::new()
+        .add_attribute("action", "set_mint_limits")
+        .add_attribute("collection", config.name)
+        .add_attribute("max_supply", limit(config.max_supply.map(|max| max.to_string())))
+        .add_attribute("max_per_wallet", limit(config.max_per_wallet.map(|max| max.to_string()))))
+}
+
+/// Sends the sender's credited revenue in `denom`, all of it when `amount`
+/// is not set.
+pub fn withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let balance = BALANCES
+        .may_load(deps.storage, (&info.sender, &denom))?
+        .unwrap_or_default();
+    let amount = amount.unwrap_or(balance);
+    if amount.is_zero() || amount > balance {
+        return Err(ContractError::InsufficientBalance { available: balance.u128(), denom });
+    }
+
+    let remaining = balance - amount;
+    if remaining.is_zero() {
+        BALANCES.remove(deps.storage, (&info.sender, &denom));
+    } else {
+        BALANCES.save(deps.storage, (&info.sender, &denom), &remaining)?;
+    }
+
+    Ok(Response::new()
+        .add_message(payout_msg(&info.sender, &denom, amount.u128())?)
+        .add_attribute("action", "withdraw")
+        .add_attribute("payee", info.sender)
+        .add_attribute("amount", format!("{}{}", amount, denom))
+        .add_attribute("remaining", remaining.to_string()))
+}
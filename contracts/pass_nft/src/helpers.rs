@@ -1,8 +1,16 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::{to_json_binary, Addr, MessageInfo,  CosmosMsg, StdResult, WasmMsg};
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Api, BankMsg, Coin, Deps, Env, MessageInfo, CosmosMsg, Order, StdResult, Storage,
+    Uint128, Uint64, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg};
 use crate::msg::{ExecuteMsg, QueryMsg, PassMsg};
 use crate::error::ContractError;
+use cw721_base_soulbound::state::TokenInfo;
+use crate::state::{
+    cw20_denom, Config, Contract, PassExtension, PaymentPolicy, PayoutMode, Tier, PASS_BY_OWNER, TIERS, RecipientRole, SplitRecipient, BALANCES, CW20_DENOM_PREFIX, TOTAL_BPS,
+};
 
 
 
@@ -41,23 +49,314 @@ pub fn convert_query_msg(msg: QueryMsg) -> Result<cw721_base_soulbound::QueryMsg
     }
 }
 
-// Function to validate payment amount in uxion
-pub fn validate_payment(info: &MessageInfo, required_price: u128) -> Result<(), ContractError> {
-    // Find payment in uxion denomination
-    let payment = info
-        .funds
-        .iter()
-        .find(|c| c.denom == "uxion")
-        .ok_or_else(|| ContractError::Custom("No uxion payment found".to_string()))?;
+/// The pass `owner` holds: the one recorded in `PASS_BY_OWNER`, or for
+/// owners whose passes predate it, the one that expires last.
+pub fn owner_pass(storage: &dyn Storage, owner: &Addr) -> StdResult<Option<(String, TokenInfo<PassExtension>)>> {
+    let contract = Contract::default();
+    if let Some(token_id) = PASS_BY_OWNER.may_load(storage, owner)? {
+        if let Some(token) = contract.tokens.may_load(storage, &token_id)? {
+            return Ok(Some((token_id, token)));
+        }
+    }
+
+    let tokens = contract
+        .tokens
+        .idx
+        .owner
+        .prefix(owner.clone())
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(tokens
+        .into_iter()
+        .max_by(|(a_id, a), (b_id, b)| (a.extension.expires_at, a_id).cmp(&(b.extension.expires_at, b_id))))
+}
+
+/// Collection settings can be changed by the minter or the wasm admin, which
+/// is the factory for the collections it creates.
+pub fn ensure_collection_admin(deps: Deps, env: &Env, sender: &Addr, config: &Config) -> Result<(), ContractError> {
+    if *sender == config.minter {
+        return Ok(());
+    }
+    ensure_wasm_admin(deps, env, sender)
+}
+
+/// Only the wasm admin may pause the collection, as pausing follows the
+/// collection status kept by the factory.
+pub fn ensure_wasm_admin(deps: Deps, env: &Env, sender: &Addr) -> Result<(), ContractError> {
+    let admin = deps
+        .querier
+        .query_wasm_contract_info(env.contract.address.to_string())?
+        .admin;
+    if admin.as_deref() != Some(sender.as_str()) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// A payment for a pass, checked against the price
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payment {
+    pub denom: String,
+    pub price: u128,
+    pub paid: u128,
+}
+
+impl Payment {
+    /// Surplus returned to the payer
+    pub fn refund(&self) -> u128 {
+        self.paid - self.price
+    }
+}
+
+/// Checks `paid` covers `price`, and under `PaymentPolicy::Exact` that it
+/// does not exceed it.
+pub fn check_payment(config: &Config, denom: String, price: u128, paid: u128) -> Result<Payment, ContractError> {
+    if paid < price {
+        return Err(ContractError::InsufficientPayment { expected: price, received: paid, denom });
+    }
+    if paid > price && config.payment_policy == PaymentPolicy::Exact {
+        return Err(ContractError::InexactPayment { expected: price, received: paid, denom });
+    }
+    Ok(Payment { denom, price, paid })
+}
+
+/// Price of `periods` pass durations at `price` each
+pub fn total_price(price: u128, periods: u32) -> StdResult<u128> {
+    Ok(Uint128::new(price).checked_mul(Uint128::from(periods))?.u128())
+}
+
+/// The single coin sent with a payment
+pub fn paid_coin(info: &MessageInfo) -> Result<&Coin, ContractError> {
+    match info.funds.as_slice() {
+        [] => Err(ContractError::NoPayment {}),
+        [payment] => Ok(payment),
+        _ => Err(ContractError::MixedDenoms {}),
+    }
+}
+
+/// Returns the payment for `periods` pass durations on `tier`. Exactly one
+/// coin must be sent, in a denom the collection accepts, covering the price
+/// in that denom.
+pub fn validate_payment(
+    info: &MessageInfo,
+    config: &Config,
+    tier: Option<&Tier>,
+    periods: u32,
+) -> Result<Payment, ContractError> {
+    let payment = paid_coin(info)?;
+    let price = config
+        .tier_price_in(tier, &payment.denom)
+        .ok_or_else(|| ContractError::UnsupportedDenom { denom: payment.denom.clone() })?;
+
+    check_payment(config, payment.denom.clone(), total_price(price, periods)?, payment.amount.u128())
+}
+
+/// Returns the payment for `periods` pass durations made with `amount` of
+/// the CW20 token at `token`. Tiers cannot be paid for in CW20 tokens.
+pub fn validate_cw20_payment(
+    config: &Config,
+    tier: Option<&Tier>,
+    token: &Addr,
+    amount: Uint128,
+    periods: u32,
+) -> Result<Payment, ContractError> {
+    let denom = cw20_denom(token);
+    let price = match tier {
+        Some(_) => None,
+        None => config.cw20_price(token),
+    }
+    .ok_or_else(|| ContractError::UnsupportedDenom { denom: denom.clone() })?;
+
+    check_payment(config, denom, total_price(price, periods)?, amount.u128())
+}
+
+/// What `remaining` seconds of a pass are worth on `tier`, in `pass_denom`
+pub fn remaining_value(config: &Config, tier: Option<&Tier>, remaining: u64) -> u128 {
+    let price = config.tier_price_in(tier, &config.pass_denom).unwrap_or_default();
+    let (duration, _) = config.tier_terms(tier);
+    Uint128::new(price).multiply_ratio(remaining, duration).u128()
+}
+
+/// Seconds of a pass that `value`, in `pass_denom`, buys on `tier`. Time on
+/// a free tier has no price, so no seconds.
+pub fn value_time(config: &Config, tier: Option<&Tier>, value: u128) -> StdResult<u64> {
+    let price = config.tier_price_in(tier, &config.pass_denom).unwrap_or_default();
+    if price == 0 {
+        return Ok(0);
+    }
+    let (duration, _) = config.tier_terms(tier);
+    Ok(Uint64::try_from(Uint128::new(value).multiply_ratio(duration, price))?.u64())
+}
+
+/// Passes need a lifetime, with a grace period no longer than it.
+pub fn validate_pass_terms(pass_duration: u64, grace_period: u64) -> Result<(), ContractError> {
+    if pass_duration == 0 {
+        return Err(ContractError::InvalidPassDuration {});
+    }
+    if grace_period > pass_duration {
+        return Err(ContractError::InvalidGracePeriod {});
+    }
+    Ok(())
+}
 
-    // Check if payment amount meets required price
-    if payment.amount.u128() < required_price {
-        return Err(ContractError::Custom(format!(
-            "Insufficient payment. Required: {} uxion, provided: {} uxion",
-            required_price,
-            payment.amount
-        )));
+/// Tiers need a name and a duration to price time on them, and the
+/// duration has to fit in the prepaid limit for the tier to be renewable.
+pub fn validate_tier(config: &Config, name: &str, tier: &Tier) -> Result<(), ContractError> {
+    if name.trim().is_empty() || tier.duration == 0 {
+        return Err(ContractError::InvalidTier {});
     }
+    if tier.grace_period > tier.duration {
+        return Err(ContractError::InvalidGracePeriod {});
+    }
+    if config.max_prepaid_duration.is_some_and(|max| max < tier.duration) {
+        return Err(ContractError::InvalidPrepaidLimit {});
+    }
+    Ok(())
+}
+
+/// A prepaid limit has to leave room for at least one renewal, on the
+/// collection's own terms and on every tier.
+pub fn validate_prepaid_limit(storage: &dyn Storage, config: &Config) -> Result<(), ContractError> {
+    let Some(max) = config.max_prepaid_duration else {
+        return Ok(());
+    };
+    if max < config.pass_duration {
+        return Err(ContractError::InvalidPrepaidLimit {});
+    }
+    for tier in TIERS.range(storage, None, None, Order::Ascending) {
+        let (_, tier) = tier?;
+        if max < tier.duration {
+            return Err(ContractError::InvalidPrepaidLimit {});
+        }
+    }
+    Ok(())
+}
+
+/// Checks a denom has the shape the bank module accepts, which covers
+/// native, `ibc/<hash>` and `factory/<creator>/<subdenom>` denoms. The
+/// `cw20:` prefix is kept for CW20 revenue in `BALANCES`.
+pub fn validate_denom(denom: &str) -> Result<(), ContractError> {
+    let mut chars = denom.chars();
+    let valid = (3..=128).contains(&denom.len())
+        && !denom.starts_with(CW20_DENOM_PREFIX)
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | ':' | '.' | '_' | '-'));
+    if !valid {
+        return Err(ContractError::InvalidDenom { denom: denom.to_string() });
+    }
+    Ok(())
+}
 
+/// Additional prices need valid denoms, each listed once and distinct from
+/// the primary `pass_denom`.
+pub fn validate_additional_prices(pass_denom: &str, prices: &[Coin]) -> Result<(), ContractError> {
+    for (i, price) in prices.iter().enumerate() {
+        validate_denom(&price.denom)?;
+        if price.denom == pass_denom || prices[..i].iter().any(|other| other.denom == price.denom) {
+            return Err(ContractError::DuplicateDenom { denom: price.denom.clone() });
+        }
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// CW20 prices need valid token addresses, each listed once.
+pub fn validate_cw20_prices(api: &dyn Api, prices: Vec<Cw20Coin>) -> Result<Vec<Cw20CoinVerified>, ContractError> {
+    let mut verified: Vec<Cw20CoinVerified> = Vec::with_capacity(prices.len());
+    for price in prices {
+        let address = api.addr_validate(&price.address)?;
+        if verified.iter().any(|other| other.address == address) {
+            return Err(ContractError::DuplicateDenom { denom: cw20_denom(&address) });
+        }
+        verified.push(Cw20CoinVerified { address, amount: price.amount });
+    }
+    Ok(verified)
+}
+
+/// A split must cover exactly `TOTAL_BPS`, without empty shares, and name an
+/// address for every role but the house and the artist.
+pub fn validate_revenue_split(api: &dyn Api, revenue_split: &[SplitRecipient]) -> Result<(), ContractError> {
+    let mut total = 0u32;
+    for recipient in revenue_split {
+        if recipient.bps == 0 {
+            return Err(ContractError::InvalidRevenueSplit {});
+        }
+        match (&recipient.address, recipient.role) {
+            (Some(address), _) => {
+                api.addr_validate(address.as_str())?;
+            }
+            (None, RecipientRole::House | RecipientRole::Artist) => {}
+            (None, _) => return Err(ContractError::InvalidRevenueSplit {}),
+        }
+        total = total.saturating_add(recipient.bps);
+    }
+    if total != TOTAL_BPS {
+        return Err(ContractError::InvalidRevenueSplit {});
+    }
+    Ok(())
+}
+
+/// Shares of `amount` along the revenue split. Shares are rounded down and
+/// the remainder goes to the first recipient, so no dust stays behind.
+pub fn revenue_shares(config: &Config, amount: u128) -> Vec<(Addr, u128)> {
+    let shares: Vec<u128> = config
+        .revenue_split
+        .iter()
+        .map(|recipient| Uint128::new(amount).multiply_ratio(recipient.bps, TOTAL_BPS).u128())
+        .collect();
+    let remainder = amount - shares.iter().sum::<u128>();
+
+    config
+        .revenue_split
+        .iter()
+        .zip(shares)
+        .enumerate()
+        .map(|(i, (recipient, share))| (config.payee(recipient), if i == 0 { share + remainder } else { share }))
+        .filter(|(_, share)| *share > 0)
+        .collect()
+}
+
+/// Sends `amount` of `denom` to `recipient`, as a CW20 transfer for
+/// `cw20:<token>` denoms and as a bank send otherwise.
+pub fn payout_msg(recipient: &Addr, denom: &str, amount: u128) -> StdResult<CosmosMsg> {
+    match denom.strip_prefix(CW20_DENOM_PREFIX) {
+        Some(token) => Ok(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(amount),
+            })?,
+            funds: vec![],
+        }
+        .into()),
+        None => Ok(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount, denom),
+        }
+        .into()),
+    }
+}
+
+/// Pays out a mint or renewal payment. In push mode this returns the sends,
+/// in pull mode the shares are credited to the payees' balances.
+pub fn distribute_revenue(
+    storage: &mut dyn Storage,
+    config: &Config,
+    denom: &str,
+    amount: u128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let shares = revenue_shares(config, amount);
+    match config.payout_mode {
+        PayoutMode::Push => shares
+            .into_iter()
+            .map(|(payee, share)| payout_msg(&payee, denom, share))
+            .collect(),
+        PayoutMode::Pull => {
+            for (payee, share) in shares {
+                BALANCES.update(storage, (&payee, denom), |balance| -> StdResult<_> {
+                    Ok(balance.unwrap_or_default().checked_add(Uint128::new(share))?)
+                })?;
+            }
+            Ok(vec![])
+        }
+    }
+}
@@ -3,7 +3,11 @@ mod tests {
     use cosmwasm_std::{Coin, Empty, Addr};
     use cw_multi_test::{App, Contract, ContractWrapper, Executor};
     use crate::contract::{execute, instantiate, query};
-    use crate::msg::{InstantiateMsg, ExecuteMsg, QueryMsg, PassMsg, ValidityResponse, ConfigResponse, PassResponse};
+    use crate::msg::{
+        InstantiateMsg, ExecuteMsg, QueryMsg, PassMsg, ValidityResponse, ConfigResponse, PassResponse,
+        ClaimableBalanceResponse, ConfigUpdate,
+    };
+    use crate::state::PayoutMode;
     use crate::msg::PassQuery;
 
     const PASS_PRICE: u128 = 10;
@@ -69,11 +73,20 @@ mod tests {
                     minter: user1.clone(),
                     collection_info,
                     pass_price: PASS_PRICE,
+                    pass_denom: None,
+                    additional_prices: None,
+                    cw20_prices: None,
+                    payment_policy: None,
+                    max_prepaid_duration: None,
+                    holder_policy: None,
+                    max_supply: None,
+                    max_per_wallet: None,
                     pass_duration: PASS_DURATION,
                     grace_period: GRACE_PERIOD,
                     payment_address: payment_addr.clone(),
                     artist_percentage: ARTIST_ROYALTY,
-                    house_percentage: HOUSE_ROYALTY
+                    house_percentage: HOUSE_ROYALTY,
+                    revenue_split: None,
                 },
                 &[],
                 "music-pass",
@@ -109,7 +122,8 @@ mod tests {
         println!("Attempting first mint for user1...");
         let mint_msg_1 = ExecuteMsg::Extension { 
             msg: PassMsg::MintPass { 
-                owner_address: user1.to_string()
+                owner_address: user1.to_string(),
+                tier: None,
             }
         };
 
@@ -163,7 +177,8 @@ mod tests {
         println!("\nAttempting second mint for user2...");
         let mint_msg_2 = ExecuteMsg::Extension { 
             msg: PassMsg::MintPass { 
-                owner_address: user2.to_string()
+                owner_address: user2.to_string(),
+                tier: None,
             }
         };
 
@@ -217,7 +232,8 @@ mod tests {
         println!("\n=== Testing Pass Renewal ===");
         let msg = ExecuteMsg::Extension { 
             msg: PassMsg::RenewPass { 
-                token_id: token_id_1.clone()
+                token_id: token_id_1.clone(),
+                periods: None,
             }
         };
         
@@ -263,4 +279,110 @@ mod tests {
             println!("Balance of {}: {}", user, balance.amount);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pull_payouts() {
+        let mut app = App::default();
+        let minter = Addr::unchecked("minter");
+        let user = Addr::unchecked("user1");
+        let artist = Addr::unchecked("artist");
+        let payment_addr = Addr::unchecked("payment_addr");
+        app.init_modules(|router, _api, storage| {
+            router.bank.init_balance(storage, &user, vec![Coin::new(1000u128, "uxion")]).unwrap();
+        });
+
+        let contract_id = app.store_code(contract_pass());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                minter.clone(),
+                &InstantiateMsg {
+                    name: "Test Pass".to_string(),
+                    symbol: COLLECTION_SYMBOL.to_string(),
+                    artist: artist.clone(),
+                    minter: minter.clone(),
+                    collection_info: "Test Collection".to_string(),
+                    pass_price: PASS_PRICE,
+                    pass_denom: None,
+                    additional_prices: None,
+                    cw20_prices: None,
+                    payment_policy: None,
+                    max_prepaid_duration: None,
+                    holder_policy: None,
+                    max_supply: None,
+                    max_per_wallet: None,
+                    pass_duration: PASS_DURATION,
+                    grace_period: GRACE_PERIOD,
+                    payment_address: payment_addr.clone(),
+                    artist_percentage: ARTIST_ROYALTY,
+                    house_percentage: HOUSE_ROYALTY,
+                    revenue_split: None,
+                },
+                &[],
+                "music-pass",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            minter.clone(),
+            contract_addr.clone(),
+            &ExecuteMsg::Extension {
+                msg: PassMsg::UpdateConfig(ConfigUpdate {
+                    payout_mode: Some(PayoutMode::Pull),
+                    ..Default::default()
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: user.to_string(), tier: None },
+        };
+        app.execute_contract(user.clone(), contract_addr.clone(), &mint_msg, &[Coin::new(PASS_PRICE, "uxion")])
+            .unwrap();
+
+        // Revenue stays in the contract until claimed
+        assert_eq!(app.wrap().query_balance(&artist, "uxion").unwrap().amount.u128(), 0);
+        assert_eq!(app.wrap().query_balance(&contract_addr, "uxion").unwrap().amount.u128(), PASS_PRICE);
+        let claimable = |app: &App, address: &Addr| {
+            let res: ClaimableBalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    contract_addr.clone(),
+                    &QueryMsg::Extension { msg: PassQuery::ClaimableBalance { address: address.to_string() } },
+                )
+                .unwrap();
+            res.balances
+        };
+        assert_eq!(claimable(&app, &artist), vec![Coin::new(7u128, "uxion")]);
+        assert_eq!(claimable(&app, &payment_addr), vec![Coin::new(3u128, "uxion")]);
+
+        let withdraw = |amount: Option<u128>| ExecuteMsg::Extension {
+            msg: PassMsg::Withdraw { denom: "uxion".to_string(), amount: amount.map(Into::into) },
+        };
+        app.execute_contract(artist.clone(), contract_addr.clone(), &withdraw(Some(5)), &[]).unwrap();
+        assert_eq!(claimable(&app, &artist), vec![Coin::new(2u128, "uxion")]);
+        let err = app
+            .execute_contract(artist.clone(), contract_addr.clone(), &withdraw(Some(5)), &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<crate::ContractError>().unwrap(),
+            crate::ContractError::InsufficientBalance { available: 2, denom: "uxion".to_string() }
+        );
+        app.execute_contract(artist.clone(), contract_addr.clone(), &withdraw(None), &[]).unwrap();
+        app.execute_contract(payment_addr.clone(), contract_addr.clone(), &withdraw(None), &[]).unwrap();
+
+        assert_eq!(app.wrap().query_balance(&artist, "uxion").unwrap().amount.u128(), 7);
+        assert_eq!(app.wrap().query_balance(&payment_addr, "uxion").unwrap().amount.u128(), 3);
+        assert!(claimable(&app, &artist).is_empty());
+        let err = app
+            .execute_contract(artist.clone(), contract_addr.clone(), &withdraw(None), &[])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast::<crate::ContractError>().unwrap(),
+            crate::ContractError::InsufficientBalance { available: 0, denom: "uxion".to_string() }
+        );
+    }
+}
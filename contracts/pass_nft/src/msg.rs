@@ -1,6 +1,7 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Timestamp, Addr};
-use crate::state::PassExtension;
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use cw20::{Cw20Coin, Cw20CoinVerified};
+use crate::state::{HolderPolicy, PassExtension, PaymentPolicy, PayoutMode, SplitRecipient, Tier};
 use cw721_base_soulbound::CustomMsg;
 
 // Custom Instantiate message for contract
@@ -12,24 +13,108 @@ pub struct InstantiateMsg {
     pub minter: Addr,
     pub collection_info: String,
     pub pass_price: u128,
+    /// Denom passes are paid in, `uxion` when omitted
+    pub pass_denom: Option<String>,
+    /// Prices in further denoms passes can be paid in
+    pub additional_prices: Option<Vec<Coin>>,
+    /// Prices in CW20 tokens passes can be paid in
+    pub cw20_prices: Option<Vec<Cw20Coin>>,
+    /// Whether overpayments are rejected or refunded, refunded when omitted
+    pub payment_policy: Option<PaymentPolicy>,
     pub pass_duration: u64,
+    /// How far ahead, in seconds, passes can be renewed. Unlimited when omitted.
+    pub max_prepaid_duration: Option<u64>,
+    /// Most passes ever minted, burned ones included. Unlimited when omitted.
+    pub max_supply: Option<u64>,
+    /// Most passes a single owner can hold. Unlimited when omitted.
+    pub max_per_wallet: Option<u32>,
+    /// What minting to an owner with an active pass does, allowed when omitted
+    pub holder_policy: Option<HolderPolicy>,
     pub grace_period: u64,
     pub payment_address: Addr,
 
     pub house_percentage: u32,
     pub artist_percentage: u32,
+    /// Split of mint and renewal payments. Defaults to the house and artist
+    /// percentages.
+    pub revenue_split: Option<Vec<SplitRecipient>>,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 // Custom Pass messages extending the base contract
 #[cw_serde]
 pub enum PassMsg {
-    MintPass {owner_address: String},
-    RenewPass { token_id: String },
+    /// Mints a pass on `tier`, or on the collection's own terms when not set
+    MintPass { owner_address: String, tier: Option<String> },
+    /// Extends a pass by `periods` pass durations, one when omitted, for the
+    /// price of each. Renewals stack on the current expiry. Passes whose grace
+    /// period has ended are burned and minted again instead.
+    RenewPass { token_id: String, periods: Option<u32> },
     BurnExpiredPass { token_id: String },
+    /// Updates the collection terms. Callable by the minter or the wasm admin,
+    /// which is the factory for factory-created collections. The payment
+    /// address, percentages and revenue split only by the wasm admin. New
+    /// house and artist percentages replace the revenue split with a two-way
+    /// split.
+    UpdateConfig(ConfigUpdate),
+    /// Moves a pass to another tier, `None` being the collection's own terms.
+    /// Upgrades keep the expiry and cost the difference in price over the
+    /// time left on the pass. Downgrades are free and extend the expiry by the
+    /// time that difference buys on the new tier. Only active passes can
+    /// change tier, a pass in its grace period is renewed first.
+    ChangeTier { token_id: String, tier: Option<String> },
+    /// Adds or replaces a tier, whose duration has to fit in the prepaid
+    /// limit. Same callers as `UpdateConfig`.
+    SetTier { name: String, tier: Tier },
+    /// Removes a tier no pass is on. Same callers as `UpdateConfig`.
+    RemoveTier { name: String },
+    /// Changes the mint limits, the ones left out are kept and 0 lifts one.
+    /// The maximum supply cannot go below the passes already minted. Same
+    /// callers as `UpdateConfig`.
+    SetMintLimits { max_supply: Option<u64>, max_per_wallet: Option<u32> },
+    /// Pauses minting and renewals. Only the wasm admin, which is the factory
+    /// for the collections it creates.
+    SetPaused { paused: bool },
+    /// Claims revenue credited in pull mode, the whole balance when
+    /// `amount` is not set
+    Withdraw { denom: String, amount: Option<Uint128> },
 }
 
 impl CustomMsg for PassMsg {}
 
+/// Message carried by a CW20 `Send` that pays for a pass in that token
+/// Collection settings to change, the ones left out are kept
+#[cw_serde]
+#[derive(Default)]
+#[serde(default)]
+pub struct ConfigUpdate {
+    pub payment_address: Option<String>,
+    pub pass_price: Option<u128>,
+    pub pass_duration: Option<u64>,
+    pub grace_period: Option<u64>,
+    pub house_percentage: Option<u32>,
+    pub artist_percentage: Option<u32>,
+    pub revenue_split: Option<Vec<SplitRecipient>>,
+    pub payout_mode: Option<PayoutMode>,
+    /// Replaces the prices in denoms other than `pass_denom`
+    pub additional_prices: Option<Vec<Coin>>,
+    /// Replaces the accepted CW20 tokens and their prices
+    pub cw20_prices: Option<Vec<Cw20Coin>>,
+    pub payment_policy: Option<PaymentPolicy>,
+    /// Replaces the prepaid limit, 0 removes it. The limit has to cover
+    /// the pass duration and the duration of every tier.
+    pub max_prepaid_duration: Option<u64>,
+    pub holder_policy: Option<HolderPolicy>,
+}
+
+#[cw_serde]
+pub enum ReceiveMsg {
+    MintPass { owner_address: String },
+    RenewPass { token_id: String, periods: Option<u32> },
+}
+
 pub type ExecuteMsg = cw721_base_soulbound::ExecuteMsg<PassExtension, PassMsg>;
 
 // Custom Pass Queries
@@ -51,6 +136,15 @@ GetConfig {},
 
 #[returns(ArtistInfoResponse)] 
 GetArtistInfo {},
+
+#[returns(ClaimableBalanceResponse)]
+ClaimableBalance { address: String },
+
+#[returns(TiersResponse)]
+Tiers {},
+
+#[returns(SupplyResponse)]
+Supply {},
 }
 
 pub type QueryMsg = cw721_base_soulbound::QueryMsg<PassQuery>;
@@ -63,6 +157,7 @@ pub struct ValidityResponse {
     pub expires_at: Timestamp,
     pub in_grace_period: bool,
     pub grace_period_end: Option<Timestamp>,
+    pub tier: Option<String>,
 }
 
 #[cw_serde]
@@ -73,6 +168,7 @@ pub struct PassResponse {
     pub owner: String,
     pub is_valid: bool,
     pub expires_at: Timestamp,
+    pub tier: Option<String>,
 }
 
 #[cw_serde]
@@ -83,16 +179,59 @@ pub struct ConfigResponse {
     pub minter: Addr,
     pub collection_info: String, 
     pub pass_price: u128, 
+    pub pass_denom: String,
     pub pass_duration: u64,
     pub grace_period: u64, 
     pub payment_address: Addr,
     pub house_percentage: u32,
     pub artist_percentage: u32, 
+    pub paused: bool,
+    pub revenue_split: Vec<SplitRecipient>,
+    pub payout_mode: PayoutMode,
+    pub additional_prices: Vec<Coin>,
+    pub cw20_prices: Vec<Cw20CoinVerified>,
+    pub payment_policy: PaymentPolicy,
+    pub max_prepaid_duration: Option<u64>,
+    pub max_supply: Option<u64>,
+    pub max_per_wallet: Option<u32>,
+    pub holder_policy: HolderPolicy,
+}
+
+#[cw_serde]
+pub struct SupplyResponse {
+    /// Passes in circulation
+    pub supply: u64,
+    /// Passes ever minted, burned ones included
+    pub minted: u64,
+    pub max_supply: Option<u64>,
+    /// Passes left to mint, unlimited when not set
+    pub remaining: Option<u64>,
+    pub max_per_wallet: Option<u32>,
+}
+
+#[cw_serde]
+pub struct TierResponse {
+    pub name: String,
+    pub tier: Tier,
+    /// Passes currently on the tier
+    pub supply: u64,
+}
+
+#[cw_serde]
+pub struct TiersResponse {
+    pub tiers: Vec<TierResponse>,
+}
+
+#[cw_serde]
+pub struct ClaimableBalanceResponse {
+    pub address: Addr,
+    pub balances: Vec<Coin>,
 }
 
 #[cw_serde]
 pub struct ArtistInfoResponse {
     pub artist: String,
     pub total_passes: u64,
+    /// Passes before their expiry, passes in their grace period are not counted
     pub active_passes: u64,
 }
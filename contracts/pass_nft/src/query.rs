@@ -1,40 +1,31 @@
-use cosmwasm_std::{Deps, Env, StdResult};
-use crate::msg::{ ValidityResponse, ConfigResponse,PassResponse };
-use crate::state::{Contract, CONFIG, PassStatus};
+use cosmwasm_std::{Coin, Deps, Env, StdResult};
+use crate::msg::{
+    ValidityResponse, ConfigResponse, PassResponse, ClaimableBalanceResponse, SupplyResponse, TierResponse, TiersResponse,
+};
+use crate::state::{active_pass_count, Contract, CONFIG, TOKEN_ID_COUNTER, PassStatus, BALANCES, TIERS, TIER_SUPPLY};
+use crate::helpers::owner_pass;
 use crate::msg::ArtistInfoResponse;
 use cosmwasm_std::StdError;
 use cosmwasm_std::Order;
 
 
+/// The owner's most recently minted pass, see `owner_pass`
 pub fn get_user_pass(
     deps: Deps,
     env: Env,
     symbol: String,
     owner: String,
 ) -> StdResult<PassResponse> {
-    let contract = Contract::default();
     let config = CONFIG.load(deps.storage)?;
     let owner_addr = deps.api.addr_validate(&owner)?;
 
     // Format the expected token_id pattern
     let token_id_prefix = format!("{}-", symbol.to_lowercase());
-    
-    // Query tokens by owner
-    let tokens: Vec<String> = contract.tokens
-        .idx
-        .owner
-        .prefix(owner_addr.clone())
-        .keys(deps.storage, None, None, Order::Ascending)
-        .collect::<StdResult<Vec<_>>>()?;
 
-    // Find the token that matches our symbol
-    let matching_token = tokens
-        .into_iter()
-        .find(|token_id| token_id.starts_with(&token_id_prefix))
+    let (matching_token, token) = owner_pass(deps.storage, &owner_addr)?
+        .filter(|(token_id, _)| token_id.starts_with(&token_id_prefix))
         .ok_or_else(|| StdError::not_found("No pass found for this symbol and owner"))?;
 
-    // Get token details
-    let token = contract.tokens.load(deps.storage, &matching_token)?;
     let status = token.extension.status(env.block.time);
     let is_valid = matches!(status, PassStatus::Active | PassStatus::InGracePeriod);
 
@@ -45,6 +36,7 @@ pub fn get_user_pass(
         token_id: matching_token,
         is_valid,
         expires_at: token.extension.expires_at,
+        tier: token.extension.tier,
     })
 }
 
@@ -63,31 +55,19 @@ pub fn query_validity(deps: Deps, env: Env, token_id: String) -> StdResult<Valid
         expires_at: token.extension.expires_at,
         in_grace_period: matches!(status, PassStatus::InGracePeriod),
         grace_period_end: if is_valid { Some(token.extension.grace_period_end) } else { None },
+        tier: token.extension.tier,
     })
 }
 
-// New query handler for artist info
-pub fn query_artist_info(deps: Deps) -> StdResult<ArtistInfoResponse> {
+/// Passes held and, of those, passes that have not expired at the block time
+pub fn query_artist_info(deps: Deps, env: Env) -> StdResult<ArtistInfoResponse> {
     let config = CONFIG.load(deps.storage)?;
     let contract = Contract::default();
-    let total_tokens = contract.token_count(deps.storage)?;
-    
-    // Count active passes
-    let active_passes = contract.tokens
-        .range(deps.storage, None, None, Order::Ascending)
-        .filter(|item| {
-            if let Ok((_, token)) = item {
-                token.extension.is_active
-            } else {
-                false
-            }
-        })
-        .count() as u64;
 
     Ok(ArtistInfoResponse {
         artist: config.artist.to_string(),
-        total_passes: total_tokens,
-        active_passes,
+        total_passes: contract.token_count(deps.storage)?,
+        active_passes: active_pass_count(deps.storage, env.block.time)?,
     })
 }
 
@@ -100,11 +80,58 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         artist: config.artist,
         minter: config.minter,
         pass_price: config.pass_price,
+        pass_denom: config.pass_denom,
         pass_duration: config.pass_duration,
         grace_period: config.grace_period,
         payment_address: config.payment_address,
         house_percentage: config.house_percentage,
-        artist_percentage: config.artist_percentage
-        
+        artist_percentage: config.artist_percentage,
+        paused: config.paused,
+        revenue_split: config.revenue_split,
+        payout_mode: config.payout_mode,
+        additional_prices: config.additional_prices,
+        cw20_prices: config.cw20_prices,
+        payment_policy: config.payment_policy,
+        max_prepaid_duration: config.max_prepaid_duration,
+        max_supply: config.max_supply,
+        max_per_wallet: config.max_per_wallet,
+        holder_policy: config.holder_policy,
+    })
+}
+
+pub fn query_claimable_balance(deps: Deps, address: String) -> StdResult<ClaimableBalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balances = BALANCES
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(ClaimableBalanceResponse { address, balances })
+}
+
+pub fn query_tiers(deps: Deps) -> StdResult<TiersResponse> {
+    let tiers = TIERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (name, tier) = item?;
+            let supply = TIER_SUPPLY.may_load(deps.storage, &name)?.unwrap_or_default();
+            Ok(TierResponse { name, tier, supply })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TiersResponse { tiers })
+}
+
+pub fn query_supply(deps: Deps) -> StdResult<SupplyResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let minted = TOKEN_ID_COUNTER.load(deps.storage)?;
+
+    Ok(SupplyResponse {
+        supply: Contract::default().token_count(deps.storage)?,
+        minted,
+        max_supply: config.max_supply,
+        remaining: config.max_supply.map(|max_supply| max_supply.saturating_sub(minted)),
+        max_per_wallet: config.max_per_wallet,
     })
 }
@@ -1,6 +1,8 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Timestamp};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Coin, Empty, Order, StdResult, Storage, Timestamp, Uint128, Uint64};
+use cw20::Cw20CoinVerified;
+use cw_storage_plus::{Bound, Item, Map};
+use semver::Version;
 
 // Import Custom Message
 use crate::msg::PassMsg;
@@ -9,6 +11,19 @@ use crate::msg::PassMsg;
 pub const CONFIG: Item<Config> = Item::new("config");
 pub const TOKEN_ID_COUNTER: Item<u64> = Item::new("token_id_counter");
 
+/// Denom of collections instantiated without `pass_denom`
+pub const DEFAULT_DENOM: &str = "uxion";
+
+/// Prefix of the ledger denom of CW20 revenue, followed by the token address
+pub const CW20_DENOM_PREFIX: &str = "cw20:";
+
+/// Basis points a revenue split adds up to
+pub const TOTAL_BPS: u32 = 10_000;
+
+fn default_pass_denom() -> String {
+    DEFAULT_DENOM.to_string()
+}
+
 
 // Define metadata for NFTs
 #[cw_serde]
@@ -17,6 +32,9 @@ pub struct PassExtension {
     pub is_active: bool,
     pub grace_period_end: Timestamp,
     pub times_renewed: u32,
+    /// Tier the pass is on, the collection's own terms when not set
+    #[serde(default)]
+    pub tier: Option<String>,
 }
 
 pub type Contract<'a> = cw721_base_soulbound::Cw721Contract<'a, PassExtension, PassMsg, PassMsg, PassMsg>;
@@ -31,18 +49,200 @@ pub struct Config {
     pub minter: Addr,
     pub collection_info: String, 
     pub pass_price: u128, 
+    #[serde(default = "default_pass_denom")]
+    pub pass_denom: String,
     pub pass_duration: u64,
     pub grace_period: u64, 
     //royalty code 
+    /// Rounded summary of the house share in `revenue_split`
     pub house_percentage: u32,
     pub artist_percentage: u32,
     pub payment_address: Addr, 
+    /// Set by the factory when the collection is deactivated or archived
+    #[serde(default)]
+    pub paused: bool,
+    /// How mint and renewal payments are paid out
+    #[serde(default)]
+    pub revenue_split: Vec<SplitRecipient>,
+    #[serde(default)]
+    pub payout_mode: PayoutMode,
+    /// Prices in denoms other than `pass_denom`, such as IBC or
+    /// token-factory denoms
+    #[serde(default)]
+    pub additional_prices: Vec<Coin>,
+    /// CW20 tokens passes can be paid in, with their prices
+    #[serde(default)]
+    pub cw20_prices: Vec<Cw20CoinVerified>,
+    #[serde(default)]
+    pub payment_policy: PaymentPolicy,
+    /// How far past the current time, in seconds, a pass can be renewed
+    #[serde(default)]
+    pub max_prepaid_duration: Option<u64>,
+    /// Most passes ever minted, burned ones included, unlimited when not set
+    #[serde(default)]
+    pub max_supply: Option<u64>,
+    /// Most passes a single owner can hold, unlimited when not set
+    #[serde(default)]
+    pub max_per_wallet: Option<u32>,
+    #[serde(default)]
+    pub holder_policy: HolderPolicy,
+}
+
+/// What minting to an owner who already holds an active pass does
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum HolderPolicy {
+    /// Another pass is minted
+    #[default]
+    Allow,
+    /// The mint is rejected
+    Reject,
+    /// The existing pass is renewed instead, on the same terms as `RenewPass`.
+    /// Like the mint, the renewal can be paid for by anyone.
+    Renew,
+}
+
+/// What happens to a payment above the pass price
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum PaymentPolicy {
+    /// Payments must match the price exactly
+    Exact,
+    /// The surplus is refunded to the payer with the mint or renewal
+    #[default]
+    RefundExcess,
+}
+
+/// Whether revenue is sent out right away or credited to `BALANCES`
+#[cw_serde]
+#[derive(Default, Copy)]
+pub enum PayoutMode {
+    /// Each share is sent with the mint or renewal
+    #[default]
+    Push,
+    /// Shares are credited and payees claim them with `Withdraw`
+    Pull,
+}
+
+impl Config {
+    /// Price of a pass in `denom`, if the collection accepts it
+    pub fn price_in(&self, denom: &str) -> Option<u128> {
+        if denom == self.pass_denom {
+            return Some(self.pass_price);
+        }
+        self.additional_prices
+            .iter()
+            .find(|price| price.denom == denom)
+            .map(|price| price.amount.u128())
+    }
+
+    /// Price of a pass in the CW20 token at `token`, if the collection accepts it
+    pub fn cw20_price(&self, token: &Addr) -> Option<u128> {
+        self.cw20_prices
+            .iter()
+            .find(|price| price.address == *token)
+            .map(|price| price.amount.u128())
+    }
+
+    /// Price of a pass on `tier`, or on the collection's own terms, in
+    /// `denom`. Tiers are priced in `pass_denom` only.
+    pub fn tier_price_in(&self, tier: Option<&Tier>, denom: &str) -> Option<u128> {
+        match tier {
+            Some(tier) => (denom == self.pass_denom).then_some(tier.price),
+            None => self.price_in(denom),
+        }
+    }
+
+    /// Duration and grace period of a pass on `tier`, or on the collection's
+    /// own terms
+    pub fn tier_terms(&self, tier: Option<&Tier>) -> (u64, u64) {
+        match tier {
+            Some(tier) => (tier.duration, tier.grace_period),
+            None => (self.pass_duration, self.grace_period),
+        }
+    }
+
+    /// Sets the split and keeps the house and artist percentages in line with it
+    pub fn set_revenue_split(&mut self, revenue_split: Vec<SplitRecipient>) {
+        let house_bps: u32 = revenue_split
+            .iter()
+            .filter(|recipient| recipient.role == RecipientRole::House)
+            .map(|recipient| recipient.bps)
+            .sum();
+        self.house_percentage = house_bps / 100;
+        self.artist_percentage = 100 - self.house_percentage;
+        self.revenue_split = revenue_split;
+    }
+
+    /// Address a recipient is paid at
+    pub fn payee(&self, recipient: &SplitRecipient) -> Addr {
+        match (&recipient.address, recipient.role) {
+            (Some(address), _) => address.clone(),
+            (None, RecipientRole::House) => self.payment_address.clone(),
+            // Other roles always carry an address, see `validate_revenue_split`
+            (None, _) => self.artist.clone(),
+        }
+    }
+}
+
+/// Who a share of the revenue is for
+#[cw_serde]
+#[derive(Copy)]
+pub enum RecipientRole {
+    House,
+    Artist,
+    Producer,
+    Label,
+    Featured,
+}
+
+/// Share of every mint and renewal payment, in basis points
+#[cw_serde]
+pub struct SplitRecipient {
+    pub role: RecipientRole,
+    /// Defaults to `payment_address` for the house and to `artist` for the
+    /// artist. Required for every other role.
+    pub address: Option<Addr>,
+    pub bps: u32,
+}
+
+/// The two-way split collections had before revenue splits. The artist is
+/// listed first so that it keeps receiving the rounding remainder.
+pub fn default_revenue_split(house_percentage: u32, artist_percentage: u32) -> Vec<SplitRecipient> {
+    [(RecipientRole::Artist, artist_percentage), (RecipientRole::House, house_percentage)]
+        .into_iter()
+        .filter(|(_, percentage)| *percentage > 0)
+        .map(|(role, percentage)| SplitRecipient { role, address: None, bps: percentage * 100 })
+        .collect()
+}
+
+/// Membership level sold alongside the collection's own terms
+#[cw_serde]
+pub struct Tier {
+    /// Price in `pass_denom`
+    pub price: u128,
+    pub duration: u64,
+    /// At most `duration`
+    pub grace_period: u64,
+    /// Most passes on the tier at once, unlimited when not set
+    pub max_supply: Option<u64>,
+}
+
+/// Tiers by name
+pub const TIERS: Map<&str, Tier> = Map::new("tiers");
+
+/// Passes currently on each tier
+pub const TIER_SUPPLY: Map<&str, u64> = Map::new("tier_supply");
+
+/// Denom CW20 revenue is credited under in `BALANCES`
+pub fn cw20_denom(token: &Addr) -> String {
+    format!("{}{}", CW20_DENOM_PREFIX, token)
 }
 
 /// Additional helpers for managing PassExtension logic
 impl PassExtension {
     /// Create a new pass with proper timestamps
-    pub fn new(current_time: Timestamp, pass_duration: u64, grace_period: u64) -> Self {
+    pub fn new(current_time: Timestamp, pass_duration: u64, grace_period: u64, tier: Option<String>) -> Self {
         let expires_at = current_time.plus_seconds(pass_duration);
         let grace_period_end = expires_at.plus_seconds(grace_period);
 
@@ -51,6 +251,7 @@ impl PassExtension {
             is_active: true,
             grace_period_end,
             times_renewed: 0,
+            tier,
         }
     }
 
@@ -65,9 +266,16 @@ impl PassExtension {
         }
     }
 
+    /// Expiry after renewing for `periods`. Renewals stack on the current
+    /// expiry, or start from `current_time` once it has passed.
+    pub fn renewed_expiry(&self, current_time: Timestamp, pass_duration: u64, periods: u32) -> StdResult<Timestamp> {
+        let seconds = Uint64::new(pass_duration).checked_mul(Uint64::from(periods))?;
+        Ok(self.expires_at.max(current_time).plus_seconds(seconds.u64()))
+    }
+
     /// Handle renewal of a pass
-    pub fn renew(&mut self, current_time: Timestamp, pass_duration: u64, grace_period: u64) {
-        self.expires_at = current_time.plus_seconds(pass_duration);
+    pub fn renew(&mut self, expires_at: Timestamp, grace_period: u64) {
+        self.expires_at = expires_at;
         self.grace_period_end = self.expires_at.plus_seconds(grace_period);
         self.is_active = true;
         self.times_renewed += 1;
@@ -83,4 +291,89 @@ pub enum PassStatus {
 }
 
 
-pub const TOKENS_BY_OWNER: Map<&Addr, Vec<String>> = Map::new("tokens_by_owner");
+/// Most recently minted pass of each owner
+pub const PASS_BY_OWNER: Map<&Addr, String> = Map::new("pass_by_owner");
+
+/// Revenue waiting to be withdrawn, by payee and denom
+pub const BALANCES: Map<(&Addr, &str), Uint128> = Map::new("balances");
+
+/// Seconds covered by each bucket of `EXPIRING_PASSES`
+pub const EXPIRY_BUCKET: u64 = 86_400;
+
+/// Passes by the day they expire on, in days since the epoch
+pub const EXPIRING_PASSES: Map<u64, u64> = Map::new("expiring_passes");
+
+/// Passes by expiry, in seconds, and token id
+pub const PASS_EXPIRIES: Map<(u64, &str), Empty> = Map::new("pass_expiries");
+
+/// Moves `token_id` in the expiry index from its `previous` expiry to
+/// `expires_at`, `None` standing for a pass that was not or is no longer held
+pub fn index_expiry(
+    storage: &mut dyn Storage,
+    token_id: &str,
+    previous: Option<Timestamp>,
+    expires_at: Option<Timestamp>,
+) -> StdResult<()> {
+    if let Some(previous) = previous {
+        PASS_EXPIRIES.remove(storage, (previous.seconds(), token_id));
+        let day = previous.seconds() / EXPIRY_BUCKET;
+        match EXPIRING_PASSES.may_load(storage, day)?.unwrap_or_default() {
+            0 | 1 => EXPIRING_PASSES.remove(storage, day),
+            count => EXPIRING_PASSES.save(storage, day, &(count - 1))?,
+        }
+    }
+    if let Some(expires_at) = expires_at {
+        PASS_EXPIRIES.save(storage, (expires_at.seconds(), token_id), &Empty {})?;
+        let day = expires_at.seconds() / EXPIRY_BUCKET;
+        let count = EXPIRING_PASSES.may_load(storage, day)?.unwrap_or_default();
+        EXPIRING_PASSES.save(storage, day, &(count + 1))?;
+    }
+    Ok(())
+}
+
+/// Passes with `PassStatus::Active` at `now`. Reads the day counts after
+/// the current day and the passes expiring on it, not every token.
+pub fn active_pass_count(storage: &dyn Storage, now: Timestamp) -> StdResult<u64> {
+    let day = now.seconds() / EXPIRY_BUCKET;
+    let later_days = EXPIRING_PASSES
+        .range(storage, Some(Bound::exclusive(day)), None, Order::Ascending)
+        .map(|item| item.map(|(_, count)| count))
+        .sum::<StdResult<u64>>()?;
+    let rest_of_day = PASS_EXPIRIES
+        .keys(
+            storage,
+            Some(Bound::inclusive((now.seconds() + 1, ""))),
+            Some(Bound::exclusive(((day + 1) * EXPIRY_BUCKET, ""))),
+            Order::Ascending,
+        )
+        .count() as u64;
+    Ok(later_days + rest_of_day)
+}
+
+/// Brings state written by `from` up to the current version
+pub fn migrate_state(storage: &mut dyn Storage, from: &Version) -> StdResult<()> {
+    // 0.2.0: revenue is paid out along a basis-point split. Collections from
+    // before it pay out along their house and artist percentages. Active
+    // passes are counted from an expiry index.
+    if *from < Version::new(0, 2, 0) {
+        let mut config = CONFIG.load(storage)?;
+        if config.revenue_split.is_empty() {
+            let revenue_split = default_revenue_split(config.house_percentage, config.artist_percentage);
+            config.set_revenue_split(revenue_split);
+            CONFIG.save(storage, &config)?;
+        }
+
+        let expiries = Contract::default()
+            .tokens
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(token_id, token)| (token_id, token.extension.expires_at)))
+            .collect::<StdResult<Vec<_>>>()?;
+        for (token_id, expires_at) in expiries {
+            if !PASS_EXPIRIES.has(storage, (expires_at.seconds(), &token_id)) {
+                index_expiry(storage, &token_id, None, Some(expires_at))?;
+            }
+        }
+    }
+
+    Ok(())
+}
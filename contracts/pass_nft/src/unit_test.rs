@@ -1,16 +1,23 @@
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::{
-        from_json, Addr, coins, OwnedDeps,
+        from_json, to_json_binary, Addr, BankMsg, coin, coins, Coin, ContractInfoResponse, CosmosMsg, ContractResult, OwnedDeps,
+        SystemResult, Uint128, WasmMsg, WasmQuery,
         testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
     };
 
+    use cw20::{Cw20Coin, Cw20CoinVerified, Cw20ExecuteMsg, Cw20ReceiveMsg};
+
     use crate::{
-        contract::{instantiate, execute, query},
+        contract::{instantiate, execute, query, migrate},
+        error::ContractError,
         msg::{
-            InstantiateMsg, ExecuteMsg, QueryMsg, PassMsg, PassQuery, 
-            ConfigResponse, ValidityResponse, ArtistInfoResponse, PassResponse
+            MigrateMsg,
+            InstantiateMsg, ExecuteMsg, QueryMsg, PassMsg, PassQuery, ConfigUpdate,
+            ConfigResponse, ValidityResponse, ArtistInfoResponse, PassResponse, ReceiveMsg, TiersResponse,
+            SupplyResponse,
         },
+        state::{active_pass_count, index_expiry, HolderPolicy, PaymentPolicy, RecipientRole, SplitRecipient, Tier, CONFIG},
     };
 
     // Constants for testing
@@ -25,6 +32,18 @@ mod tests {
     const HOUSE_ROYALTY: u32 = 30;
     const ARTIST_ROYALTY : u32 = 70; 
 
+    // Makes "factory" the wasm admin, as it is for factory-created collections
+    fn set_factory_admin(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { .. } => {
+                let mut res = ContractInfoResponse::default();
+                res.admin = Some("factory".to_string());
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&res).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+    }
+
     // Helper function to setup contract
     fn setup_contract() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
         let mut deps = mock_dependencies();
@@ -40,11 +59,20 @@ mod tests {
             minter, 
             collection_info,
             pass_price: PASS_PRICE,
+            pass_denom: None,
+            additional_prices: None,
+            cw20_prices: None,
+            payment_policy: None,
+            max_prepaid_duration: None,
+            holder_policy: None,
+            max_supply: None,
+            max_per_wallet: None,
             pass_duration: PASS_DURATION,
             grace_period: GRACE_PERIOD,
             payment_address,
             house_percentage : HOUSE_ROYALTY,
-            artist_percentage: ARTIST_ROYALTY
+            artist_percentage: ARTIST_ROYALTY,
+            revenue_split: None,
         };
 
         let info = mock_info(ARTIST, &[]);
@@ -69,11 +97,20 @@ mod tests {
             minter: minter.clone(),
             collection_info,
             pass_price: PASS_PRICE,
+            pass_denom: None,
+            additional_prices: None,
+            cw20_prices: None,
+            payment_policy: None,
+            max_prepaid_duration: None,
+            holder_policy: None,
+            max_supply: None,
+            max_per_wallet: None,
             pass_duration: PASS_DURATION,
             grace_period: GRACE_PERIOD,
             payment_address: payment_address.clone(),
             house_percentage:HOUSE_ROYALTY,
-            artist_percentage: ARTIST_ROYALTY
+            artist_percentage: ARTIST_ROYALTY,
+            revenue_split: None,
         };
 
         let info = mock_info(ARTIST, &[]);
@@ -88,6 +125,7 @@ mod tests {
         assert_eq!(config.name, COLLECTION_NAME);
         assert_eq!(config.symbol, COLLECTION_SYMBOL);
         assert_eq!(config.pass_price, PASS_PRICE);
+        assert_eq!(config.pass_denom, "uxion");
         assert_eq!(config.pass_duration, PASS_DURATION);
         assert_eq!(config.grace_period, GRACE_PERIOD);
         assert_eq!(config.payment_address, payment_address);
@@ -102,10 +140,11 @@ mod tests {
         let msg = ExecuteMsg::Extension { 
             msg: PassMsg::MintPass { 
                 owner_address: USER.to_string(),
+                tier: None,
             } 
         };
         let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
-        assert_eq!(err.to_string(), "No uxion payment found");
+        assert_eq!(err, ContractError::NoPayment {});
 
         // Mint with correct payment
         let info = mock_info(USER, &coins(PASS_PRICE, "uxion"));
@@ -154,6 +193,7 @@ mod tests {
         let mint_msg = ExecuteMsg::Extension { 
             msg: PassMsg::MintPass { 
                 owner_address: USER.to_string(),
+                tier: None,
             } 
         };
         let mint_res = execute(deps.as_mut(), mock_env(), info, mint_msg).unwrap();
@@ -168,12 +208,81 @@ mod tests {
         // Renew with correct payment
         let info = mock_info(USER, &coins(PASS_PRICE, "uxion"));
         let msg = ExecuteMsg::Extension { 
-            msg: PassMsg::RenewPass { token_id: token_id.clone() } 
+            msg: PassMsg::RenewPass { token_id: token_id.clone(), periods: None } 
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert!(res.attributes.iter().any(|attr| attr.key == "action" && attr.value == "renew_pass"));
     }
 
+    #[test]
+    fn test_renewal_stacking() {
+        let mut deps = setup_contract();
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: USER.to_string(), tier: None },
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg).unwrap();
+        let token_id = format!("{}-1", COLLECTION_SYMBOL.to_lowercase());
+        let renew = |periods: Option<u32>| ExecuteMsg::Extension {
+            msg: PassMsg::RenewPass { token_id: token_id.clone(), periods },
+        };
+        let expires_at = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| {
+            let validity: ValidityResponse = from_json(
+                query(deps.as_ref(), mock_env(), QueryMsg::Extension {
+                    msg: PassQuery::CheckValidity { token_id: token_id.clone() },
+                }).unwrap()
+            ).unwrap();
+            validity.expires_at
+        };
+        let minted_at = mock_env().block.time;
+
+        // Renewing early keeps the time left on the pass
+        let mut env = mock_env();
+        env.block.time = minted_at.plus_seconds(PASS_DURATION / 2);
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(PASS_PRICE, "uxion")), renew(None)).unwrap();
+        assert_eq!(expires_at(&deps), minted_at.plus_seconds(2 * PASS_DURATION));
+
+        // Several periods cost the price of each
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(2 * PASS_PRICE, "uxion")), renew(Some(3)))
+            .unwrap_err();
+        assert_eq!(err, ContractError::InsufficientPayment {
+            expected: 3 * PASS_PRICE,
+            received: 2 * PASS_PRICE,
+            denom: "uxion".to_string(),
+        });
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(PASS_PRICE, "uxion")), renew(Some(0)))
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidRenewalPeriods {});
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(3 * PASS_PRICE, "uxion")), renew(Some(3)))
+            .unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "periods" && attr.value == "3"));
+        assert_eq!(expires_at(&deps), minted_at.plus_seconds(5 * PASS_DURATION));
+
+        // The prepaid limit caps how far ahead a pass can run
+        let set_limit = |max_prepaid_duration: u64| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                max_prepaid_duration: Some(max_prepaid_duration),
+                ..Default::default()
+            }),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), set_limit(PASS_DURATION - 1)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPrepaidLimit {});
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), set_limit(5 * PASS_DURATION)).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(PASS_PRICE, "uxion")), renew(None))
+            .unwrap_err();
+        assert_eq!(err, ContractError::PrepaidLimitExceeded { max_prepaid_duration: 5 * PASS_DURATION });
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), set_limit(0)).unwrap();
+
+        // Within the grace period a renewal starts from now
+        env.block.time = minted_at.plus_seconds(5 * PASS_DURATION + GRACE_PERIOD);
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(PASS_PRICE, "uxion")), renew(None)).unwrap();
+        assert_eq!(expires_at(&deps), env.block.time.plus_seconds(PASS_DURATION));
+
+        // Once the grace period has ended the pass has to be minted again
+        env.block.time = env.block.time.plus_seconds(PASS_DURATION + GRACE_PERIOD + 1);
+        let err = execute(deps.as_mut(), env, mock_info(USER, &coins(PASS_PRICE, "uxion")), renew(None)).unwrap_err();
+        assert_eq!(err, ContractError::PassExpiredAndGracePeriodEnded {});
+    }
+
     #[test]
     fn test_burn_expired_pass() {
         let mut deps = setup_contract();
@@ -183,6 +292,7 @@ mod tests {
         let mint_msg = ExecuteMsg::Extension { 
             msg: PassMsg::MintPass { 
                 owner_address: USER.to_string(),
+                tier: None,
             } 
         };
         let mint_res = execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
@@ -225,15 +335,702 @@ mod tests {
         let mint_msg = ExecuteMsg::Extension { 
             msg: PassMsg::MintPass { 
                 owner_address: USER.to_string(),
+                tier: None,
             } 
         };
         execute(deps.as_mut(), mock_env(), info, mint_msg).unwrap();
 
         // Query updated artist info
-        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), msg.clone()).unwrap();
         let info = from_json::<ArtistInfoResponse>(&res).unwrap();
         assert_eq!(info.artist, ARTIST);
         assert_eq!(info.total_passes, 1);
         assert_eq!(info.active_passes, 1);
+
+        // A pass minted later outlives the first one
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(PASS_DURATION / 2);
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: ARTIST.to_string(), tier: None },
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(ARTIST, &coins(PASS_PRICE, "uxion")), mint_msg).unwrap();
+
+        // The first pass is in its grace period, which is not counted as active
+        env.block.time = mock_env().block.time.plus_seconds(PASS_DURATION);
+        let info: ArtistInfoResponse = from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+        assert_eq!((info.total_passes, info.active_passes), (2, 1));
+
+        // Renewing moves the first pass back to active
+        let renew_msg = ExecuteMsg::Extension {
+            msg: PassMsg::RenewPass { token_id: "test-1".to_string(), periods: None },
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(PASS_PRICE, "uxion")), renew_msg).unwrap();
+        let info: ArtistInfoResponse = from_json(query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+        assert_eq!(info.active_passes, 2);
+
+        env.block.time = env.block.time.plus_seconds(3 * PASS_DURATION);
+        let info: ArtistInfoResponse = from_json(query(deps.as_ref(), env, msg).unwrap()).unwrap();
+        assert_eq!((info.total_passes, info.active_passes), (2, 0));
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut deps = setup_contract();
+
+        // Instantiation records the pass_nft name, not the cw721 base one
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.contract, "crates.io:loop_music");
+
+        // Refuse a downgrade
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:loop_music", "99.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::MigrationDowngrade { .. }));
+
+        // Refuse a different contract
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:other", "0.1.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidMigrationContract { .. }));
+
+        // Collections instantiated before the cw2 fix carry the base contract
+        // name and version, and predate revenue splits
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:cw721-soulbound", "1.0.2").unwrap();
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.revenue_split = vec![];
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.revenue_split.len(), 2);
+
+        // Upgrade from 0.1.0, whose config had no revenue split and which
+        // kept no expiry index
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: USER.to_string(), tier: None },
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg).unwrap();
+        let expires_at = mock_env().block.time.plus_seconds(PASS_DURATION);
+        index_expiry(deps.as_mut().storage, "test-1", Some(expires_at), None).unwrap();
+        assert_eq!(active_pass_count(deps.as_ref().storage, mock_env().block.time).unwrap(), 0);
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:loop_music", "0.1.0").unwrap();
+        let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+        config.revenue_split = vec![];
+        CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(active_pass_count(deps.as_ref().storage, mock_env().block.time).unwrap(), 1);
+        let config = CONFIG.load(deps.as_ref().storage).unwrap();
+        assert_eq!(config.revenue_split, vec![
+            SplitRecipient { role: RecipientRole::Artist, address: None, bps: 7000 },
+            SplitRecipient { role: RecipientRole::House, address: None, bps: 3000 },
+        ]);
+        assert!(res.attributes.iter().any(|attr| attr.key == "from_version" && attr.value == "0.1.0"));
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_update_config() {
+        let mut deps = setup_contract();
+        set_factory_admin(&mut deps);
+
+        let update = |house_percentage: u32, artist_percentage: u32| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                payment_address: Some("treasury".to_string()),
+                pass_price: Some(20),
+                house_percentage: Some(house_percentage),
+                artist_percentage: Some(artist_percentage),
+                ..Default::default()
+            }),
+        };
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(ARTIST, &[]), update(20, 80)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), update(20, 70)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidRoyalties {});
+
+        execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), update(20, 80)).unwrap();
+        let config: ConfigResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::GetConfig {} }).unwrap()
+        ).unwrap();
+        assert_eq!(config.payment_address, Addr::unchecked("treasury"));
+        assert_eq!(config.pass_price, 20);
+        assert_eq!(config.pass_duration, PASS_DURATION);
+        assert_eq!((config.house_percentage, config.artist_percentage), (20, 80));
+
+        // The minter sets the terms, but not where the revenue goes
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(30, 70)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let redirect = ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                payment_address: Some(USER.to_string()),
+                ..Default::default()
+            }),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), redirect).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let reprice = ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate { pass_price: Some(25), ..Default::default() }),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), reprice).unwrap();
+
+        // Passes keep a lifetime and a grace period no longer than it
+        let terms = |pass_duration: Option<u64>, grace_period: Option<u64>| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate { pass_duration, grace_period, ..Default::default() }),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), terms(Some(0), None)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPassDuration {});
+        let err = execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), terms(None, Some(PASS_DURATION + 1)))
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidGracePeriod {});
+        let err = execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), terms(Some(GRACE_PERIOD - 1), None))
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidGracePeriod {});
+    }
+
+    #[test]
+    fn test_set_paused() {
+        let mut deps = setup_contract();
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: USER.to_string(), tier: None },
+        };
+        let pause = |paused: bool| ExecuteMsg::Extension { msg: PassMsg::SetPaused { paused } };
+        set_factory_admin(&mut deps);
+
+        // Pausing follows the factory's collection status, so the minter cannot pause
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), pause(true)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), pause(true)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::CollectionPaused {});
+
+        let config: ConfigResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::GetConfig {} }).unwrap()
+        ).unwrap();
+        assert!(config.paused);
+
+        execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), pause(false)).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg).unwrap();
+    }
+
+    #[test]
+    fn test_revenue_split() {
+        let mut deps = setup_contract();
+        set_factory_admin(&mut deps);
+        let update = |house_percentage: Option<u32>, revenue_split: Option<Vec<SplitRecipient>>| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                house_percentage,
+                artist_percentage: house_percentage.map(|house| 100 - house),
+                revenue_split,
+                ..Default::default()
+            }),
+        };
+        let recipient = |role: RecipientRole, address: Option<&str>, bps: u32| SplitRecipient {
+            role,
+            address: address.map(Addr::unchecked),
+            bps,
+        };
+        let sends = |res: &cosmwasm_std::Response| {
+            res.messages
+                .iter()
+                .map(|msg| match &msg.msg {
+                    cosmwasm_std::CosmosMsg::Bank(cosmwasm_std::BankMsg::Send { to_address, amount }) => {
+                        (to_address.clone(), amount[0].amount.u128())
+                    }
+                    other => panic!("unexpected message {:?}", other),
+                })
+                .collect::<Vec<_>>()
+        };
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: USER.to_string(), tier: None },
+        };
+
+        // The default split keeps the old rounding: the artist gets the remainder
+        execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), update(Some(35), None)).unwrap();
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg.clone()).unwrap();
+        assert_eq!(sends(&res), vec![(ARTIST.to_string(), 7), (PAYMENT_ADDR.to_string(), 3)]);
+
+        let invalid = [
+            vec![recipient(RecipientRole::Artist, None, 9000)],
+            vec![recipient(RecipientRole::Artist, None, 9000), recipient(RecipientRole::Producer, None, 1000)],
+            vec![recipient(RecipientRole::Artist, None, 10000), recipient(RecipientRole::Label, Some("label"), 0)],
+        ];
+        for revenue_split in invalid {
+            let err = execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), update(None, Some(revenue_split))).unwrap_err();
+            assert_eq!(err, ContractError::InvalidRevenueSplit {});
+        }
+        let split = vec![
+            recipient(RecipientRole::Artist, None, 6000),
+            recipient(RecipientRole::House, None, 2550),
+            recipient(RecipientRole::Producer, Some("producer"), 1000),
+            recipient(RecipientRole::Featured, Some("featured"), 450),
+        ];
+        let err = execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), update(Some(20), Some(split.clone()))).unwrap_err();
+        assert_eq!(err, ContractError::InvalidRoyalties {});
+        execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), update(None, Some(split.clone()))).unwrap();
+
+        let config: ConfigResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::GetConfig {} }).unwrap()
+        ).unwrap();
+        assert_eq!(config.revenue_split, split);
+        assert_eq!((config.house_percentage, config.artist_percentage), (25, 75));
+
+        // 6 + 2 + 1 + 0 is paid out in shares, the remaining 1 goes to the first recipient
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg).unwrap();
+        let payouts = vec![
+            (ARTIST.to_string(), 7),
+            (PAYMENT_ADDR.to_string(), 2),
+            ("producer".to_string(), 1),
+        ];
+        assert_eq!(sends(&res), payouts);
+
+        // Renewals pay out the same way
+        let renew_msg = ExecuteMsg::Extension {
+            msg: PassMsg::RenewPass { token_id: "test-2".to_string(), periods: None },
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), renew_msg).unwrap();
+        assert_eq!(sends(&res), payouts);
+
+        // Percentages go back to a two-way split
+        execute(deps.as_mut(), mock_env(), mock_info("factory", &[]), update(Some(40), None)).unwrap();
+        let config: ConfigResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::GetConfig {} }).unwrap()
+        ).unwrap();
+        assert_eq!(config.revenue_split, vec![
+            recipient(RecipientRole::Artist, None, 6000),
+            recipient(RecipientRole::House, None, 4000),
+        ]);
+    }
+
+    #[test]
+    fn test_multi_denom_payments() {
+        let mut deps = setup_contract();
+        const USDC: &str = "ibc/498A0751C798A0D9A389AA3691123DADA57DAA4FE165D5C75894505B876BA6E4";
+        const LOOP: &str = "factory/xion1creator/uloop";
+        let update = |additional_prices: Vec<Coin>| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                additional_prices: Some(additional_prices),
+                ..Default::default()
+            }),
+        };
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: USER.to_string(), tier: None },
+        };
+
+        let invalid = [
+            (vec![coin(5, "ux")], ContractError::InvalidDenom { denom: "ux".to_string() }),
+            (vec![coin(5, "1usdc")], ContractError::InvalidDenom { denom: "1usdc".to_string() }),
+            (vec![coin(5, "uxion")], ContractError::DuplicateDenom { denom: "uxion".to_string() }),
+            (vec![coin(5, USDC), coin(6, USDC)], ContractError::DuplicateDenom { denom: USDC.to_string() }),
+        ];
+        for (prices, expected) in invalid {
+            let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(prices)).unwrap_err();
+            assert_eq!(err, expected);
+        }
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(vec![coin(5, USDC), coin(100, LOOP)])).unwrap();
+
+        // Revenue is split in the denom that was paid
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(5, USDC)), mint_msg.clone()).unwrap();
+        let paid: Vec<_> = res.messages.iter().map(|msg| match &msg.msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount[0].clone(),
+            other => panic!("unexpected message {:?}", other),
+        }).collect();
+        assert_eq!(paid, vec![coin(4, USDC), coin(1, USDC)]);
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(100, LOOP)), mint_msg.clone()).unwrap();
+
+        let cases = [
+            (vec![coin(PASS_PRICE, "uxion"), coin(5, USDC)], ContractError::MixedDenoms {}),
+            (coins(10, "uatom"), ContractError::UnsupportedDenom { denom: "uatom".to_string() }),
+            (
+                coins(99, LOOP),
+                ContractError::InsufficientPayment { expected: 100, received: 99, denom: LOOP.to_string() },
+            ),
+        ];
+        for (funds, expected) in cases {
+            let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &funds), mint_msg.clone()).unwrap_err();
+            assert_eq!(err, expected);
+        }
+
+        let config: ConfigResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::GetConfig {} }).unwrap()
+        ).unwrap();
+        assert_eq!(config.additional_prices, vec![coin(5, USDC), coin(100, LOOP)]);
+    }
+
+    #[test]
+    fn test_cw20_payments() {
+        let mut deps = setup_contract();
+        const FAN_TOKEN: &str = "fan_token";
+        let receive = |sender: &str, amount: u128, msg: ReceiveMsg| ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_json_binary(&msg).unwrap(),
+        });
+        let mint = || ReceiveMsg::MintPass { owner_address: USER.to_string() };
+        let token_info = mock_info(FAN_TOKEN, &[]);
+
+        // Tokens are only taken once the collection accepts them
+        let err = execute(deps.as_mut(), mock_env(), token_info.clone(), receive(USER, 50, mint())).unwrap_err();
+        assert_eq!(err, ContractError::UnsupportedDenom { denom: format!("cw20:{}", FAN_TOKEN) });
+
+        let update = |cw20_prices: Vec<Cw20Coin>| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                cw20_prices: Some(cw20_prices),
+                ..Default::default()
+            }),
+        };
+        let price = Cw20Coin { address: FAN_TOKEN.to_string(), amount: Uint128::new(50) };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(vec![price.clone(), price.clone()]))
+            .unwrap_err();
+        assert_eq!(err, ContractError::DuplicateDenom { denom: format!("cw20:{}", FAN_TOKEN) });
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update(vec![price])).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), token_info.clone(), receive(USER, 49, mint())).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientPayment {
+            expected: 50,
+            received: 49,
+            denom: format!("cw20:{}", FAN_TOKEN),
+        });
+
+        // Proceeds are split with CW20 transfers along the same split as native
+        // payments, and the surplus goes back to the sender
+        let res = execute(deps.as_mut(), mock_env(), token_info.clone(), receive(USER, 55, mint())).unwrap();
+        let transfers: Vec<_> = res.messages.iter().map(|msg| match &msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, funds }) => {
+                assert_eq!(contract_addr, FAN_TOKEN);
+                assert!(funds.is_empty());
+                from_json::<Cw20ExecuteMsg>(msg).unwrap()
+            }
+            other => panic!("unexpected message {:?}", other),
+        }).collect();
+        assert_eq!(transfers, vec![
+            Cw20ExecuteMsg::Transfer { recipient: ARTIST.to_string(), amount: Uint128::new(35) },
+            Cw20ExecuteMsg::Transfer { recipient: PAYMENT_ADDR.to_string(), amount: Uint128::new(15) },
+            Cw20ExecuteMsg::Transfer { recipient: USER.to_string(), amount: Uint128::new(5) },
+        ]);
+
+        // Only the owner or the minter, as the token sender, can renew
+        let token_id = format!("{}-1", COLLECTION_SYMBOL.to_lowercase());
+        let renew = || ReceiveMsg::RenewPass { token_id: token_id.clone(), periods: None };
+        execute(deps.as_mut(), mock_env(), token_info.clone(), receive(USER, 50, renew())).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), token_info, receive("stranger", 50, renew())).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let config: ConfigResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::GetConfig {} }).unwrap()
+        ).unwrap();
+        assert_eq!(config.cw20_prices, vec![
+            Cw20CoinVerified { address: Addr::unchecked(FAN_TOKEN), amount: Uint128::new(50) },
+        ]);
+    }
+
+    #[test]
+    fn test_payment_policy() {
+        let mut deps = setup_contract();
+        let mint_msg = ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: USER.to_string(), tier: None },
+        };
+        let payment_attributes = |res: &cosmwasm_std::Response| {
+            ["price", "paid", "refunded"]
+                .map(|key| res.attributes.iter().find(|attr| attr.key == key).unwrap().value.clone())
+        };
+
+        // Overpayments are refunded to the sender by default
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(100, "uxion")), mint_msg.clone()).unwrap();
+        assert_eq!(payment_attributes(&res), ["10uxion", "100uxion", "90uxion"]);
+        assert_eq!(res.messages.last().unwrap().msg, CosmosMsg::Bank(BankMsg::Send {
+            to_address: USER.to_string(),
+            amount: coins(90, "uxion"),
+        }));
+        let paid_out: u128 = res.messages[..res.messages.len() - 1].iter().map(|msg| match &msg.msg {
+            CosmosMsg::Bank(BankMsg::Send { amount, .. }) => amount[0].amount.u128(),
+            other => panic!("unexpected message {:?}", other),
+        }).sum();
+        assert_eq!(paid_out, PASS_PRICE);
+
+        // Nothing is refunded for an exact payment
+        let res = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg.clone()).unwrap();
+        assert_eq!(payment_attributes(&res), ["10uxion", "10uxion", "0uxion"]);
+        assert_eq!(res.messages.len(), 2);
+
+        let update = ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                payment_policy: Some(PaymentPolicy::Exact),
+                ..Default::default()
+            }),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), update).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(11, "uxion")), mint_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::InexactPayment { expected: 10, received: 11, denom: "uxion".to_string() });
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint_msg).unwrap();
+
+        let config: ConfigResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::GetConfig {} }).unwrap()
+        ).unwrap();
+        assert_eq!(config.payment_policy, PaymentPolicy::Exact);
+    }
+
+    #[test]
+    fn test_tiers() {
+        let mut deps = setup_contract();
+        const GOLD_PRICE: u128 = 40;
+        const GOLD_DURATION: u64 = 2 * PASS_DURATION;
+        let gold = Tier { price: GOLD_PRICE, duration: GOLD_DURATION, grace_period: 600, max_supply: Some(2) };
+        let set_tier = |name: &str, tier: Tier| ExecuteMsg::Extension {
+            msg: PassMsg::SetTier { name: name.to_string(), tier },
+        };
+        let mint = |tier: Option<&str>| ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: USER.to_string(), tier: tier.map(str::to_string) },
+        };
+        let change_tier = |token_id: &str, tier: Option<&str>| ExecuteMsg::Extension {
+            msg: PassMsg::ChangeTier { token_id: token_id.to_string(), tier: tier.map(str::to_string) },
+        };
+        let validity = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, token_id: &str| -> ValidityResponse {
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Extension {
+                msg: PassQuery::CheckValidity { token_id: token_id.to_string() },
+            }).unwrap()).unwrap()
+        };
+
+        // Tiers need a duration
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_tier("gold", Tier { duration: 0, ..gold.clone() }))
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidTier {});
+        let long_grace = Tier { grace_period: GOLD_DURATION + 1, ..gold.clone() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_tier("gold", long_grace)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidGracePeriod {});
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_tier("gold", gold.clone())).unwrap();
+
+        // Every tier has to stay renewable under the prepaid limit
+        let prepaid_limit = |max_prepaid_duration: u64| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                max_prepaid_duration: Some(max_prepaid_duration),
+                ..Default::default()
+            }),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), prepaid_limit(PASS_DURATION)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPrepaidLimit {});
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), prepaid_limit(GOLD_DURATION)).unwrap();
+        let long = Tier { duration: GOLD_DURATION + 1, ..gold.clone() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_tier("long", long)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPrepaidLimit {});
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), prepaid_limit(0)).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(GOLD_PRICE, "uxion")), mint(Some("vip"))).unwrap_err();
+        assert_eq!(err, ContractError::TierNotFound { tier: "vip".to_string() });
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint(Some("gold"))).unwrap_err();
+        assert_eq!(err, ContractError::InsufficientPayment {
+            expected: GOLD_PRICE,
+            received: PASS_PRICE,
+            denom: "uxion".to_string(),
+        });
+
+        // Passes on a tier run on its terms, up to its supply cap
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(GOLD_PRICE, "uxion")), mint(Some("gold"))).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(GOLD_PRICE, "uxion")), mint(Some("gold"))).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(GOLD_PRICE, "uxion")), mint(Some("gold"))).unwrap_err();
+        assert_eq!(err, ContractError::TierSoldOut { tier: "gold".to_string() });
+        let gold_pass = validity(&deps, "test-1");
+        assert_eq!(gold_pass.tier, Some("gold".to_string()));
+        assert_eq!(gold_pass.expires_at, mock_env().block.time.plus_seconds(GOLD_DURATION));
+        assert_eq!(gold_pass.grace_period_end, Some(gold_pass.expires_at.plus_seconds(600)));
+
+        // Renewals charge the tier price and add the tier duration
+        let renew = ExecuteMsg::Extension {
+            msg: PassMsg::RenewPass { token_id: "test-1".to_string(), periods: None },
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), renew.clone()).unwrap_err();
+        assert!(matches!(err, ContractError::InsufficientPayment { expected: GOLD_PRICE, .. }));
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(GOLD_PRICE, "uxion")), renew).unwrap();
+        assert_eq!(validity(&deps, "test-1").expires_at, mock_env().block.time.plus_seconds(2 * GOLD_DURATION));
+
+        // Upgrading halfway through a base pass costs the difference over the time left
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &coins(PASS_PRICE, "uxion")), mint(None)).unwrap();
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(PASS_DURATION / 2);
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), change_tier("test-3", None)).unwrap_err();
+        assert_eq!(err, ContractError::SameTier {});
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(GOLD_PRICE, "uxion")), change_tier("test-3", Some("gold")))
+            .unwrap_err();
+        assert_eq!(err, ContractError::TierSoldOut { tier: "gold".to_string() });
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), set_tier("gold", Tier { max_supply: None, ..gold.clone() }))
+            .unwrap();
+
+        // Gold is worth 10 over the 600s left, the base pass 5
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(5, "uxion")), change_tier("test-3", Some("gold")))
+            .unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "price" && attr.value == "5uxion"));
+        let upgraded = validity(&deps, "test-3");
+        assert_eq!(upgraded.tier, Some("gold".to_string()));
+        assert_eq!(upgraded.expires_at, mock_env().block.time.plus_seconds(PASS_DURATION));
+
+        // Downgrades take no payment and turn the 5 the gold time is worth
+        // over the base pass into 600s more on it
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &coins(1, "uxion")), change_tier("test-3", None))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Custom("Changing to this tier takes no payment".to_string()));
+        let res = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), change_tier("test-3", None)).unwrap();
+        assert!(res.messages.is_empty());
+        let downgraded = validity(&deps, "test-3");
+        assert_eq!(downgraded.tier, None);
+        assert_eq!(downgraded.expires_at, mock_env().block.time.plus_seconds(PASS_DURATION + 600));
+        assert_eq!(downgraded.grace_period_end, Some(downgraded.expires_at.plus_seconds(GRACE_PERIOD)));
+
+        // Past its expiry a pass has no time left to trade, not even in its grace period
+        let mut env = mock_env();
+        env.block.time = downgraded.expires_at;
+        let err = execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), change_tier("test-3", Some("gold")))
+            .unwrap_err();
+        assert_eq!(err, ContractError::PassInGracePeriod {});
+        env.block.time = downgraded.grace_period_end.unwrap().plus_seconds(1);
+        let err = execute(deps.as_mut(), env, mock_info(USER, &[]), change_tier("test-3", Some("gold")))
+            .unwrap_err();
+        assert_eq!(err, ContractError::PassExpiredAndGracePeriodEnded {});
+
+        let tiers: TiersResponse = from_json(
+            query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::Tiers {} }).unwrap()
+        ).unwrap();
+        assert_eq!(tiers.tiers.len(), 1);
+        assert_eq!((tiers.tiers[0].name.as_str(), tiers.tiers[0].supply), ("gold", 2));
+
+        // Tiers with passes on them stay until the passes are gone
+        let remove = ExecuteMsg::Extension { msg: PassMsg::RemoveTier { name: "gold".to_string() } };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), remove.clone()).unwrap_err();
+        assert_eq!(err, ContractError::TierInUse { tier: "gold".to_string() });
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(2 * GOLD_DURATION + 601);
+        for token_id in ["test-1", "test-2"] {
+            let burn = ExecuteMsg::Extension { msg: PassMsg::BurnExpiredPass { token_id: token_id.to_string() } };
+            execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), burn).unwrap();
+        }
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), remove).unwrap();
+    }
+
+    #[test]
+    fn test_mint_limits() {
+        let mut deps = setup_contract();
+        let mint = |owner: &str| ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: owner.to_string(), tier: None },
+        };
+        let set_limits = |max_supply: Option<u64>, max_per_wallet: Option<u32>| ExecuteMsg::Extension {
+            msg: PassMsg::SetMintLimits { max_supply, max_per_wallet },
+        };
+        let supply = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| -> SupplyResponse {
+            from_json(query(deps.as_ref(), mock_env(), QueryMsg::Extension { msg: PassQuery::Supply {} }).unwrap())
+                .unwrap()
+        };
+        let paid = || mock_info(USER, &coins(PASS_PRICE, "uxion"));
+
+        assert_eq!(supply(&deps), SupplyResponse {
+            supply: 0,
+            minted: 0,
+            max_supply: None,
+            remaining: None,
+            max_per_wallet: None,
+        });
+        execute(deps.as_mut(), mock_env(), paid(), mint(USER)).unwrap();
+        execute(deps.as_mut(), mock_env(), paid(), mint("fan")).unwrap();
+
+        // The cap can be raised or lifted but not set below the passes minted
+        let err = execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_limits(Some(1), None)).unwrap_err();
+        assert_eq!(err, ContractError::MaxSupplyBelowMinted { minted: 2 });
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_limits(Some(3), Some(1))).unwrap();
+        assert_eq!(supply(&deps), SupplyResponse {
+            supply: 2,
+            minted: 2,
+            max_supply: Some(3),
+            remaining: Some(1),
+            max_per_wallet: Some(1),
+        });
+
+        // Wallets are limited by the passes they own, whoever pays
+        let err = execute(deps.as_mut(), mock_env(), paid(), mint("fan")).unwrap_err();
+        assert_eq!(err, ContractError::WalletLimitReached { max_per_wallet: 1 });
+        execute(deps.as_mut(), mock_env(), paid(), mint("other_fan")).unwrap();
+
+        let err = execute(deps.as_mut(), mock_env(), paid(), mint("new_fan")).unwrap_err();
+        assert_eq!(err, ContractError::MaxSupplyReached {});
+        assert_eq!(supply(&deps).remaining, Some(0));
+
+        // Burned passes still count against the cap
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(PASS_DURATION + GRACE_PERIOD + 1);
+        let burn = ExecuteMsg::Extension { msg: PassMsg::BurnExpiredPass { token_id: "test-1".to_string() } };
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), burn).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), paid(), mint("new_fan")).unwrap_err();
+        assert_eq!(err, ContractError::MaxSupplyReached {});
+        assert_eq!((supply(&deps).supply, supply(&deps).minted), (2, 3));
+
+        // Raising the cap alone keeps the wallet limit
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_limits(Some(5), None)).unwrap();
+        assert_eq!(supply(&deps).max_per_wallet, Some(1));
+        execute(deps.as_mut(), env.clone(), paid(), mint("new_fan")).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), paid(), mint("new_fan")).unwrap_err();
+        assert_eq!(err, ContractError::WalletLimitReached { max_per_wallet: 1 });
+
+        // 0 lifts a limit
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_limits(Some(0), Some(0))).unwrap();
+        execute(deps.as_mut(), env, paid(), mint("new_fan")).unwrap();
+        let supply = supply(&deps);
+        assert_eq!((supply.supply, supply.max_supply, supply.max_per_wallet), (4, None, None));
+    }
+
+    #[test]
+    fn test_holder_policy() {
+        let mut deps = setup_contract();
+        let mint = |owner: &str| ExecuteMsg::Extension {
+            msg: PassMsg::MintPass { owner_address: owner.to_string(), tier: None },
+        };
+        let set_policy = |holder_policy: HolderPolicy| ExecuteMsg::Extension {
+            msg: PassMsg::UpdateConfig(ConfigUpdate {
+                holder_policy: Some(holder_policy),
+                ..Default::default()
+            }),
+        };
+        let user_pass = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, env| -> PassResponse {
+            from_json(query(deps.as_ref(), env, QueryMsg::Extension {
+                msg: PassQuery::GetUserPass { symbol: COLLECTION_SYMBOL.to_string(), owner: USER.to_string() },
+            }).unwrap()).unwrap()
+        };
+        let paid = || mock_info(USER, &coins(PASS_PRICE, "uxion"));
+
+        // Owners can hold several passes by default, the latest one is theirs
+        for _ in 0..10 {
+            execute(deps.as_mut(), mock_env(), paid(), mint(USER)).unwrap();
+        }
+        assert_eq!(user_pass(&deps, mock_env()).token_id, "test-10");
+
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_policy(HolderPolicy::Reject)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), paid(), mint(USER)).unwrap_err();
+        assert_eq!(err, ContractError::OwnerHasPass { token_id: "test-10".to_string() });
+
+        // Under the renew policy a mint extends the pass the owner holds
+        execute(deps.as_mut(), mock_env(), mock_info(USER, &[]), set_policy(HolderPolicy::Renew)).unwrap();
+        let res = execute(deps.as_mut(), mock_env(), paid(), mint(USER)).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "action" && attr.value == "renew_pass"));
+        let pass = user_pass(&deps, mock_env());
+        assert_eq!(pass.token_id, "test-10");
+        assert_eq!(pass.expires_at, mock_env().block.time.plus_seconds(2 * PASS_DURATION));
+
+        // Whoever pays for a mint to the owner pays for the renewal
+        let res = execute(deps.as_mut(), mock_env(), mock_info("fan", &coins(PASS_PRICE, "uxion")), mint(USER)).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "action" && attr.value == "renew_pass"));
+        assert_eq!(user_pass(&deps, mock_env()).expires_at, mock_env().block.time.plus_seconds(3 * PASS_DURATION));
+        let renew = ExecuteMsg::Extension {
+            msg: PassMsg::RenewPass { token_id: "test-10".to_string(), periods: None },
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("fan", &coins(PASS_PRICE, "uxion")), renew).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Once every pass is past its grace period a new one is minted
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(3 * PASS_DURATION + GRACE_PERIOD + 1);
+        let res = execute(deps.as_mut(), env.clone(), paid(), mint(USER)).unwrap();
+        assert!(res.attributes.iter().any(|attr| attr.key == "token_id" && attr.value == "test-11"));
+
+        // Burning the recorded pass falls back to the pass that expires last
+        env.block.time = env.block.time.plus_seconds(PASS_DURATION + GRACE_PERIOD + 1);
+        let burn = ExecuteMsg::Extension { msg: PassMsg::BurnExpiredPass { token_id: "test-11".to_string() } };
+        execute(deps.as_mut(), env.clone(), mock_info(USER, &[]), burn).unwrap();
+        assert_eq!(user_pass(&deps, env).token_id, "test-10");
     }
-}
\ No newline at end of file
+}